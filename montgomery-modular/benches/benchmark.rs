@@ -0,0 +1,81 @@
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use montgomery_modular::Montgomery;
+use rand::Rng;
+
+const N: u32 = 998244353;
+const LEN: usize = 1 << 16;
+
+fn random_vec(len: usize) -> Vec<u32> {
+    let mut rng = rand::thread_rng();
+    (0..len).map(|_| rng.gen_range(0..N)).collect()
+}
+
+fn benchmark(c: &mut Criterion) {
+    let m = Montgomery::<{ 1 << 32 }>::new(N).unwrap();
+    let a = random_vec(LEN);
+    let b = random_vec(LEN);
+
+    let mut group = c.benchmark_group("multiply");
+    group.bench_function("scalar", |bench| {
+        bench.iter_batched_ref(
+            || a.clone(),
+            |x| {
+                for (x, &y) in x.iter_mut().zip(&b) {
+                    *x = m.multiply(*x, y);
+                }
+            },
+            BatchSize::SmallInput,
+        )
+    });
+    group.bench_function("slice", |bench| {
+        bench.iter_batched_ref(
+            || a.clone(),
+            |x| m.multiply_slice(x, &b),
+            BatchSize::SmallInput,
+        )
+    });
+    group.finish();
+
+    let mut group = c.benchmark_group("pow");
+    let a = random_vec(LEN / 16);
+    group.bench_function("scalar", |bench| {
+        bench.iter_batched_ref(
+            || a.clone(),
+            |x| {
+                for x in x.iter_mut() {
+                    *x = m.pow(*x, black_box(N as u64 - 2));
+                }
+            },
+            BatchSize::SmallInput,
+        )
+    });
+    group.bench_function("slice", |bench| {
+        bench.iter_batched_ref(
+            || a.clone(),
+            |x| m.pow_slice(x, black_box(N as u64 - 2)),
+            BatchSize::SmallInput,
+        )
+    });
+    group.finish();
+
+    let mut group = c.benchmark_group("reduce");
+    let mut rng = rand::thread_rng();
+    let wide = (0..LEN)
+        .map(|_| rng.gen_range(0..(N as u64) << 32))
+        .collect::<Vec<_>>();
+    let mut out = vec![0; LEN];
+    group.bench_function("scalar", |bench| {
+        bench.iter(|| {
+            for (&x, y) in wide.iter().zip(out.iter_mut()) {
+                *y = m.reduce(x);
+            }
+        })
+    });
+    group.bench_function("slice", |bench| {
+        bench.iter(|| m.reduce_slice(&wide, &mut out))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, benchmark);
+criterion_main!(benches);
@@ -0,0 +1,298 @@
+use std::collections::HashMap;
+
+use crate::{
+    factorize, gcd_ext, is_prime_u32,
+    prime::{gcd, R32},
+    Montgomery,
+};
+
+/// Arithmetic modulo a runtime `m`, using Montgomery reduction when `m` is odd.
+struct Context {
+    m: u32,
+    mont: Option<Montgomery<R32>>,
+}
+
+impl Context {
+    fn new(m: u32) -> Self {
+        Self {
+            m,
+            mont: Montgomery::new(m).ok(),
+        }
+    }
+
+    fn mul(&self, a: u32, b: u32) -> u32 {
+        match &self.mont {
+            Some(mont) => mont.multiply(a, b),
+            None => (a as u64 * b as u64 % self.m as u64) as u32,
+        }
+    }
+
+    fn pow(&self, a: u32, mut exp: u64) -> u32 {
+        if let Some(mont) = &self.mont {
+            return mont.pow(a, exp);
+        }
+        let mut result = 1 % self.m;
+        let mut base = a;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = self.mul(result, base);
+            }
+            base = self.mul(base, base);
+            exp >>= 1;
+        }
+        result
+    }
+
+    /// Returns `None` if `a` and `m` are not coprime.
+    fn inv(&self, a: u32) -> Option<u32> {
+        let (g, x, _) = gcd_ext(a as i64, self.m as i64);
+        (g == 1).then(|| x.rem_euclid(self.m as i64) as u32)
+    }
+
+    /// Find the smallest `x` in `0..bound` such that `base^x = target` by baby-step giant-step.
+    /// Returns `None` if `base` is not invertible.
+    fn bsgs(&self, base: u32, target: u32, bound: u64) -> Option<u64> {
+        let n = (bound as f64).sqrt() as u64 + 1;
+        // `target * base^(-j)` for `0 <= j < n`
+        let base_inv = self.inv(base)?;
+        let mut baby = HashMap::with_capacity(n as usize);
+        let mut cur = target;
+        for j in 0..n {
+            baby.entry(cur).or_insert(j);
+            cur = self.mul(cur, base_inv);
+        }
+        let giant = self.pow(base, n);
+        let mut cur = 1 % self.m;
+        for i in 0..n {
+            if let Some(&j) = baby.get(&cur) {
+                let x = i * n + j;
+                return (x < bound).then_some(x);
+            }
+            cur = self.mul(cur, giant);
+        }
+        None
+    }
+}
+
+/// Returns the smallest `x >= 0` such that `a^x ≡ b (mod m)`, or `None` if there is none.
+///
+/// `a` and `m` need not be coprime. Runs in `O(sqrt(m))` time.
+pub fn discrete_log(a: u32, b: u32, m: u32) -> Option<u64> {
+    if m == 1 {
+        return Some(0);
+    }
+    let (mut a, mut b, mut m) = (a % m, b % m, m);
+    // Divide out the common factors of `a` and `m`:
+    // `a^x ≡ b (mod m)` ⇔ `(a / g) * a^(x - 1) ≡ b / g (mod m / g)` for `x >= 1`.
+    let mut k = 1 % m;
+    let mut add = 0;
+    loop {
+        if b == k {
+            return Some(add);
+        }
+        let g = gcd(a as u64, m as u64) as u32;
+        if g == 1 {
+            break;
+        }
+        if b % g != 0 {
+            return None;
+        }
+        b /= g;
+        m /= g;
+        k = (k as u64 * (a / g) as u64 % m as u64) as u32;
+        add += 1;
+    }
+    // `k * a^x ≡ b (mod m)` with `gcd(a, m) = 1` and `gcd(k, m) = 1`
+    a %= m;
+    let ctx = Context::new(m);
+    let target = ctx.mul(b, ctx.inv(k)?);
+    ctx.bsgs(a, target, m as u64).map(|x| x + add)
+}
+
+/// Returns `x` such that `x^2 ≡ a (mod p)` by Cipolla's algorithm, or `None` if there is none.
+///
+/// `p` must be prime. Either of the two roots may be returned.
+pub fn sqrt_mod(a: u32, p: u32) -> Option<u32> {
+    assert!(is_prime_u32(p), "p must be prime.");
+    let a = a % p;
+    if a == 0 || p == 2 {
+        return Some(a);
+    }
+    let ctx = Context::new(p);
+    if ctx.pow(a, (p as u64 - 1) / 2) != 1 {
+        return None;
+    }
+    // Find `t` such that `w = t^2 - a` is a quadratic nonresidue.
+    let (t, w) = (0..p)
+        .map(|t| {
+            (
+                t,
+                ((ctx.mul(t, t) as u64 + (p - a) as u64) % p as u64) as u32,
+            )
+        })
+        .find(|&(_, w)| ctx.pow(w, (p as u64 - 1) / 2) == p - 1)
+        .unwrap();
+    // Compute `(t + sqrt(w))^((p + 1) / 2)` in `F_p[sqrt(w)]`.
+    let mul = |(x0, x1): (u32, u32), (y0, y1): (u32, u32)| {
+        let re = (ctx.mul(x0, y0) as u64 + ctx.mul(ctx.mul(x1, y1), w) as u64) % p as u64;
+        let im = (ctx.mul(x0, y1) as u64 + ctx.mul(x1, y0) as u64) % p as u64;
+        (re as u32, im as u32)
+    };
+    let mut result = (1, 0);
+    let mut base = (t, 1);
+    let mut exp = (p as u64).div_ceil(2);
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul(result, base);
+        }
+        base = mul(base, base);
+        exp >>= 1;
+    }
+    Some(result.0)
+}
+
+/// Returns `x` such that `x^q ≡ a (mod p)` by the Adleman–Manders–Miller algorithm,
+/// when `q` is a prime dividing `p - 1` and `a` is a `q`-th power residue.
+fn prime_root(ctx: &Context, a: u32, q: u64) -> u32 {
+    let p = ctx.m as u64;
+    if a == 0 {
+        return 0;
+    }
+    // p - 1 = q^s * t
+    let mut s = 0;
+    let mut t = p - 1;
+    while t.is_multiple_of(q) {
+        t /= q;
+        s += 1;
+    }
+    // Find `z` which is not a `q`-th power residue. `gamma = z^t` generates the Sylow `q`-subgroup.
+    let z = (2..p as u32)
+        .find(|&z| ctx.pow(z, (p - 1) / q) != 1)
+        .unwrap();
+    let gamma = ctx.pow(z, t);
+    // x0 = a^alpha with `q * alpha ≡ 1 (mod t)`,
+    // so that `x0^q = a * e` where `e = a^(q * alpha - 1)` lies in the subgroup of order `q^(s - 1)`.
+    let alpha = if t == 1 {
+        0
+    } else {
+        gcd_ext((q % t) as i64, t as i64).1.rem_euclid(t as i64) as u64
+    };
+    let x0 = ctx.pow(a, alpha);
+    let e = ctx.mul(ctx.pow(x0, q), ctx.inv(a).unwrap());
+    // Find `j` such that `beta^j = e^(-1)` where `beta = gamma^q`, digit by digit in base `q`.
+    let h = ctx.inv(e).unwrap();
+    let beta = ctx.pow(gamma, q);
+    let zeta = ctx.pow(gamma, q.pow(s - 1)); // order `q`
+    let mut j = 0;
+    let mut qi = 1;
+    for i in 0..s - 1 {
+        // (h * beta^(-j))^(q^(s - 2 - i)) = zeta^(digit)
+        let rest = ctx.mul(h, ctx.inv(ctx.pow(beta, j)).unwrap());
+        let y = ctx.pow(rest, q.pow(s - 2 - i));
+        let digit = ctx.bsgs(zeta, y, q).unwrap();
+        j += digit * qi;
+        qi *= q;
+    }
+    ctx.mul(x0, ctx.pow(gamma, j))
+}
+
+/// Returns `x` such that `x^k ≡ a (mod p)`, or `None` if there is none.
+///
+/// `p` must be prime. Any of the roots may be returned. `0^0` is regarded as `1`.
+pub fn kth_root_mod(a: u32, k: u64, p: u32) -> Option<u32> {
+    assert!(is_prime_u32(p), "p must be prime.");
+    let a = a % p;
+    if k == 0 {
+        return (a == 1).then_some(1);
+    }
+    if a == 0 {
+        return Some(0);
+    }
+    let ctx = Context::new(p);
+    let order = p as u64 - 1;
+    let g = gcd(k, order);
+    if ctx.pow(a, order / g) != 1 {
+        return None;
+    }
+    // Take the `g`-th root `y` of `a`, then `x = y^u` with `u * (k / g) ≡ 1 (mod (p - 1) / g)`.
+    let mut y = a;
+    for q in factorize(g) {
+        y = prime_root(&ctx, y, q);
+    }
+    let m = order / g;
+    let u = if m == 1 {
+        0
+    } else {
+        gcd_ext(((k / g) % m) as i64, m as i64)
+            .1
+            .rem_euclid(m as i64) as u64
+    };
+    Some(ctx.pow(y, u))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pow_naive(a: u32, exp: u64, m: u32) -> u32 {
+        (0..exp).fold(1 % m as u64, |acc, _| acc * a as u64 % m as u64) as u32
+    }
+
+    #[test]
+    fn discrete_log_test() {
+        for m in 1..60 {
+            for a in 0..m {
+                for b in 0..m {
+                    let expected = (0..2 * m as u64).find(|&x| pow_naive(a, x, m) == b);
+                    assert_eq!(discrete_log(a, b, m), expected, "{a}^x = {b} mod {m}");
+                }
+            }
+        }
+        assert_eq!(
+            discrete_log(3, 3u32.pow(19) % 998244353, 998244353),
+            Some(19)
+        );
+        let x = discrete_log(5, 123456789, 1_000_000_007).unwrap();
+        assert_eq!(Context::new(1_000_000_007).pow(5, x), 123456789);
+        assert_eq!(discrete_log(2, 1 << 20, 1 << 31), Some(20));
+        assert_eq!(discrete_log(2, 3, 1 << 31), None);
+    }
+
+    #[test]
+    fn sqrt_mod_test() {
+        for p in [2, 3, 5, 7, 11, 13, 17, 97, 101] {
+            for a in 0..p {
+                let expected = (0..p).any(|x| x * x % p == a);
+                match sqrt_mod(a, p) {
+                    Some(x) => assert_eq!(x as u64 * x as u64 % p as u64, a as u64),
+                    None => assert!(!expected),
+                }
+            }
+        }
+        let p = 998244353;
+        let x = sqrt_mod(123456789u64.pow(2).rem_euclid(p as u64) as u32, p).unwrap();
+        assert!(x == 123456789 || x == p - 123456789);
+    }
+
+    #[test]
+    fn kth_root_mod_test() {
+        for p in [2, 3, 5, 7, 13, 17, 31, 37, 73, 97] {
+            for k in 0..20 {
+                for a in 0..p {
+                    let expected = (0..p).any(|x| pow_naive(x, k, p) == a);
+                    match kth_root_mod(a, k, p) {
+                        Some(x) => assert_eq!(pow_naive(x, k, p), a, "{x}^{k} = {a} mod {p}"),
+                        None => assert!(!expected, "{a} has a {k}-th root mod {p}"),
+                    }
+                }
+            }
+        }
+        let p = 998244353;
+        let ctx = Context::new(p);
+        for k in [2, 7, 17, 119, 1 << 23, 998244352, 1 << 40] {
+            let a = ctx.pow(31415926, k);
+            let x = kth_root_mod(a, k, p).unwrap();
+            assert_eq!(ctx.pow(x, k), a);
+        }
+    }
+}
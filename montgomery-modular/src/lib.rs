@@ -1,3 +1,18 @@
+pub mod discrete;
+pub mod modint;
+pub mod prime;
+#[cfg(target_arch = "x86_64")]
+mod simd;
+
+pub use discrete::{discrete_log, kth_root_mod, sqrt_mod};
+pub use modint::MontgomeryModInt;
+pub use prime::{divisors, euler_phi, factorize, is_prime_u32, is_prime_u64, primitive_root};
+
+use std::{
+    error::Error,
+    fmt::{self, Display},
+};
+
 pub struct Montgomery<const R: u64> {
     n: u32,
     n_prime: u32,
@@ -12,9 +27,9 @@ pub fn gcd_ext(a: i64, b: i64) -> (i64, i64, i64) {
         Greater => (a, 1),
     };
     let (mut r1, mut x1) = match b.cmp(&0) {
-        Less => (-b, -1),
+        Less => (-b, 0),
         Equal => return (a, 1, 0),
-        Greater => (b, 1),
+        Greater => (b, 0),
     };
     while r1 != 0 {
         let q = r0 / r1;
@@ -28,79 +43,111 @@ pub fn gcd_ext(a: i64, b: i64) -> (i64, i64, i64) {
     (r0, x0, (r0 - a * x0) / b)
 }
 
+/// An error returned when a Montgomery context cannot be created for the modulus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MontgomeryError {
+    /// The modulus is even, so it is not coprime to `R`.
+    EvenModulus,
+    /// The modulus is not less than `R`.
+    OutOfRange,
+}
+
+impl Display for MontgomeryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EvenModulus => write!(f, "n must be odd to be coprime to R"),
+            Self::OutOfRange => write!(f, "n must be less than R"),
+        }
+    }
+}
+
+impl Error for MontgomeryError {}
+
+/// Compute `n^(-1) mod 2^64` by Newton's iteration
+/// when `n` is odd
+const fn inv_mod_pow2(n: u64) -> u64 {
+    // `n * n ≡ 1 (mod 8)`, so the initial value is correct in the lowest 3 bits,
+    // and each iteration doubles the number of correct bits.
+    let mut x = n;
+    let mut i = 0;
+    while i < 5 {
+        x = x.wrapping_mul(2u64.wrapping_sub(n.wrapping_mul(x)));
+        i += 1;
+    }
+    x
+}
+
 impl<const R: u64> Montgomery<R> {
-    const R_IS_POWER_OF_TWO: bool = R.is_power_of_two();
-    const MASK: u64 = R - 1;
+    const MASK: u64 = {
+        assert!(
+            R.is_power_of_two() && R <= 1 << 32,
+            "R must be a power of two not greater than 2^32."
+        );
+        R - 1
+    };
     const SHIFT: u32 = R.trailing_zeros();
 
-    fn compute_n_prime(n: u32) -> u32 {
-        if Self::R_IS_POWER_OF_TWO {
-            assert_eq!(n & 1, 1, "n and r must be coprime.");
-            assert!((n as u64) < R, "n must be less than R.");
-            let mut b = 2;
-            let mut result = 1;
-            let mut r = n as u64;
-            let mut n = (n as u64) << 1;
-            while b < R {
-                if r & b == 0 {
-                    result |= b;
-                    r += n;
-                }
-                n <<= 1;
-                b <<= 1;
-            }
-            result as u32
-        } else {
-            panic!("R is not power of two");
-            // let (g, x, _) = gcd_ext(-(n as i64), R as i64);
-            // assert_eq!(g, 1, "n and R must be coprime.");
-            // x as u32
-        }
+    /// Compute `-n^(-1) mod R`
+    /// when `n` is odd
+    const fn compute_n_prime(n: u32) -> u32 {
+        (inv_mod_pow2(n as u64).wrapping_neg() & Self::MASK) as u32
     }
 
-    pub fn new(n: u32) -> Self {
-        Self {
+    pub const fn new(n: u32) -> Result<Self, MontgomeryError> {
+        if n & 1 == 0 {
+            return Err(MontgomeryError::EvenModulus);
+        }
+        if n as u64 >= R {
+            return Err(MontgomeryError::OutOfRange);
+        }
+        Ok(Self {
             n,
             n_prime: Self::compute_n_prime(n),
             r2: {
                 let n = n as u64;
                 ((R % n) * (R % n) % n) as u32
             },
-        }
+        })
+    }
+
+    pub const fn modulus(&self) -> u32 {
+        self.n
     }
 
-    fn rem_r(a: u64) -> u64 {
+    const fn rem_r(a: u64) -> u64 {
         a & Self::MASK
     }
 
-    fn div_r(a: u64) -> u64 {
+    const fn div_r(a: u64) -> u64 {
         a >> Self::SHIFT
     }
 
     /// Compute `(a * R^(-1)) % n`
     /// when `0 <= a < n * R`
-    pub fn reduce(&self, a: u64) -> u32 {
+    pub const fn reduce(&self, a: u64) -> u32 {
         let n = self.n as u64;
-        let t = Self::div_r(a + Self::rem_r(a.wrapping_mul(self.n_prime as u64)) * n);
+        let m = Self::rem_r(a.wrapping_mul(self.n_prime as u64));
+        // `a + m * n` may overflow, so add the lower part of `a` first.
+        let t = Self::div_r(a) + Self::div_r(Self::rem_r(a) + m * n);
         (if t >= n { t - n } else { t }) as u32
     }
 
     /// Compute `(a * b) % n`
     /// when `0 <= a < n` and `0 <= b < n`
-    pub fn multiply(&self, a: u32, b: u32) -> u32 {
+    pub const fn multiply(&self, a: u32, b: u32) -> u32 {
         let c = self.reduce(a as u64 * b as u64);
         self.reduce(c as u64 * self.r2 as u64)
     }
 
     /// Compute `(a * R) % n`
     /// when `0 <= a < n`
-    pub fn multiply_r(&self, a: u32) -> u32 {
+    pub const fn multiply_r(&self, a: u32) -> u32 {
         self.reduce(a as u64 * self.r2 as u64)
     }
 
     /// Compute `a^exp % n`
-    pub fn pow(&self, a: u32, mut exp: u64) -> u32 {
-        let mut result = 1;
+    pub const fn pow(&self, a: u32, mut exp: u64) -> u32 {
+        let mut result = 1 % self.n;
         let mut base = self.multiply_r(a); // a * R
         while exp > 0 {
             if exp & 1 == 1 {
@@ -111,6 +158,125 @@ impl<const R: u64> Montgomery<R> {
         }
         result
     }
+
+    /// Compute `a[i] = (a[i] * b[i]) % n` for each `i`
+    /// when `0 <= a[i] < n` and `0 <= b[i] < n`
+    ///
+    /// Uses AVX2 if the CPU supports it.
+    pub fn multiply_slice(&self, a: &mut [u32], b: &[u32]) {
+        assert_eq!(a.len(), b.len(), "a and b must have the same length.");
+        #[cfg(target_arch = "x86_64")]
+        if is_x86_feature_detected!("avx2") {
+            // SAFETY: AVX2 is available.
+            return unsafe { simd::multiply_slice(self, a, b) };
+        }
+        for (x, &y) in a.iter_mut().zip(b) {
+            *x = self.multiply(*x, y);
+        }
+    }
+
+    /// Compute `a[i] = a[i]^exp % n` for each `i`
+    /// when `0 <= a[i] < n`
+    ///
+    /// Uses AVX2 if the CPU supports it.
+    pub fn pow_slice(&self, a: &mut [u32], exp: u64) {
+        #[cfg(target_arch = "x86_64")]
+        if is_x86_feature_detected!("avx2") {
+            // SAFETY: AVX2 is available.
+            return unsafe { simd::pow_slice(self, a, exp) };
+        }
+        for x in a {
+            *x = self.pow(*x, exp);
+        }
+    }
+
+    /// Compute `out[i] = (a[i] * R^(-1)) % n` for each `i`
+    /// when `0 <= a[i] < n * R`
+    ///
+    /// Uses AVX2 if the CPU supports it.
+    pub fn reduce_slice(&self, a: &[u64], out: &mut [u32]) {
+        assert_eq!(a.len(), out.len(), "a and out must have the same length.");
+        #[cfg(target_arch = "x86_64")]
+        if is_x86_feature_detected!("avx2") {
+            // SAFETY: AVX2 is available.
+            return unsafe { simd::reduce_slice(self, a, out) };
+        }
+        for (&x, y) in a.iter().zip(out) {
+            *y = self.reduce(x);
+        }
+    }
+}
+
+/// A Montgomery context for 64-bit moduli with `R = 2^64`.
+///
+/// Intermediate products are computed in `u128`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Montgomery64 {
+    n: u64,
+    /// `n^(-1) mod R`
+    n_inv: u64,
+    r2: u64,
+}
+
+impl Montgomery64 {
+    /// Every odd `u64` is less than `R = 2^64`, so this fails only for an even `n`.
+    pub const fn new(n: u64) -> Result<Self, MontgomeryError> {
+        if n & 1 == 0 {
+            return Err(MontgomeryError::EvenModulus);
+        }
+        let n128 = n as u128;
+        Ok(Self {
+            n,
+            n_inv: inv_mod_pow2(n),
+            r2: ((u128::MAX % n128 + 1) % n128) as u64,
+        })
+    }
+
+    pub const fn modulus(&self) -> u64 {
+        self.n
+    }
+
+    /// Compute `(a * R^(-1)) % n`
+    /// when `0 <= a < n * R`
+    pub const fn reduce(&self, a: u128) -> u64 {
+        let m = (a as u64).wrapping_mul(self.n_inv);
+        let mn = m as u128 * self.n as u128;
+        // The lower halves of `a` and `m * n` are equal.
+        let (t, borrow) = ((a >> 64) as u64).overflowing_sub((mn >> 64) as u64);
+        if borrow {
+            t.wrapping_add(self.n)
+        } else {
+            t
+        }
+    }
+
+    /// Compute `(a * b) % n`
+    /// when `0 <= a < n` and `0 <= b < n`
+    pub const fn multiply(&self, a: u64, b: u64) -> u64 {
+        let c = self.reduce(a as u128 * b as u128);
+        self.reduce(c as u128 * self.r2 as u128)
+    }
+
+    /// Compute `(a * R) % n`
+    /// when `0 <= a < n`
+    pub const fn multiply_r(&self, a: u64) -> u64 {
+        self.reduce(a as u128 * self.r2 as u128)
+    }
+
+    /// Compute `a^exp % n`
+    /// when `0 <= a < n`
+    pub const fn pow(&self, a: u64, mut exp: u64) -> u64 {
+        let mut result = 1 % self.n;
+        let mut base = self.multiply_r(a); // a * R
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = self.reduce(result as u128 * base as u128);
+            }
+            base = self.reduce(base as u128 * base as u128);
+            exp >>= 1;
+        }
+        result
+    }
 }
 
 #[cfg(test)]
@@ -119,21 +285,33 @@ mod tests {
 
     #[test]
     fn new_test() {
-        let m = Montgomery::<8>::new(5);
+        let m = Montgomery::<8>::new(5).unwrap();
         assert_eq!(m.n_prime, 3);
         assert_eq!(m.r2, 4);
+        const M: Montgomery<{ 1 << 32 }> = match Montgomery::new(998244353) {
+            Ok(m) => m,
+            Err(_) => panic!(),
+        };
+        assert_eq!(M.multiply(3, 332748118), 1);
     }
 
     #[test]
     fn multiply_test() {
-        let m = Montgomery::<256>::new(167);
+        let m = Montgomery::<256>::new(167).unwrap();
         assert_eq!(m.multiply(123, 45), 123 * 45 % 167);
     }
 
     #[test]
-    #[should_panic]
-    fn new_test_panic() {
-        Montgomery::<256>::new(150);
+    fn new_test_error() {
+        assert_eq!(
+            Montgomery::<256>::new(150).err(),
+            Some(MontgomeryError::EvenModulus)
+        );
+        assert_eq!(
+            Montgomery::<256>::new(257).err(),
+            Some(MontgomeryError::OutOfRange)
+        );
+        assert_eq!(Montgomery64::new(0), Err(MontgomeryError::EvenModulus));
     }
 
     #[test]
@@ -142,7 +320,7 @@ mod tests {
         let mut rng = rand::thread_rng();
         const R: u64 = u32::MAX as u64 + 1;
         let n = rng.gen_range(1..(R / 4)) as u32 * 2 + 1;
-        let m = Montgomery::<R>::new(n);
+        let m = Montgomery::<R>::new(n).unwrap();
         for _ in 0..10000 {
             let a = rng.gen_range(0..n);
             let b = rng.gen_range(0..n);
@@ -150,9 +328,75 @@ mod tests {
         }
     }
 
+    #[test]
+    fn multiply_large_modulus() {
+        use rand::Rng;
+        let mut rng = rand::thread_rng();
+        const R: u64 = u32::MAX as u64 + 1;
+        let n = u32::MAX - 4;
+        let m = Montgomery::<R>::new(n).unwrap();
+        for _ in 0..10000 {
+            let a = rng.gen_range(0..n);
+            let b = rng.gen_range(0..n);
+            assert_eq!(m.multiply(a, b) as u64, a as u64 * b as u64 % n as u64);
+        }
+        // `n` is prime
+        assert_eq!(m.pow(3, n as u64 - 1), 1);
+    }
+
+    #[test]
+    fn slice_test() {
+        let m = Montgomery::<{ 1 << 32 }>::new(998244353).unwrap();
+        let mut a = (0..10).collect::<Vec<u32>>();
+        let b = (10..20).collect::<Vec<u32>>();
+        m.multiply_slice(&mut a, &b);
+        assert_eq!(a, (0..10).map(|i| i * (i + 10)).collect::<Vec<_>>());
+        m.pow_slice(&mut a, 998244352);
+        assert_eq!(a[0], 0);
+        assert!(a[1..].iter().all(|&x| x == 1));
+        let mut out = vec![0; 7];
+        m.reduce_slice(&[1 << 32; 7], &mut out);
+        assert_eq!(out, vec![1; 7]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn slice_length_mismatch() {
+        let m = Montgomery::<256>::new(167).unwrap();
+        m.multiply_slice(&mut [1, 2, 3], &[1, 2]);
+    }
+
+    #[test]
+    fn multiply64_random() {
+        use rand::Rng;
+        let mut rng = rand::thread_rng();
+        for n in [3, (1 << 61) - 1, u64::MAX, rng.gen::<u64>() | 1] {
+            let m = Montgomery64::new(n).unwrap();
+            for _ in 0..10000 {
+                let a = rng.gen_range(0..n);
+                let b = rng.gen_range(0..n);
+                assert_eq!(m.multiply(a, b) as u128, a as u128 * b as u128 % n as u128);
+            }
+        }
+        let m = Montgomery64::new(18446744073709551557).unwrap();
+        assert_eq!(m.pow(2, 18446744073709551556), 1);
+        assert_eq!(m.pow(12345, 0), 1);
+        assert_eq!(Montgomery64::new(1).unwrap().pow(5, 0), 0);
+    }
+
+    #[test]
+    fn gcd_ext_test() {
+        for (a, b) in [(3, 5), (12, 18), (-4, 6), (35, -15), (-7, -21), (1, 1)] {
+            let (g, x, y) = gcd_ext(a, b);
+            assert_eq!(a * x + b * y, g);
+            assert_eq!(a % g, 0);
+            assert_eq!(b % g, 0);
+        }
+    }
+
     #[test]
     fn pow_test() {
-        let m = Montgomery::<256>::new(101);
+        let m = Montgomery::<256>::new(101).unwrap();
         assert_eq!(m.pow(2, 10), 2u32.pow(10) % 101);
         assert_eq!(m.pow(43, 5), 43u32.pow(5) % 101);
     }
@@ -0,0 +1,213 @@
+use std::{
+    fmt::{self, Debug, Display},
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign},
+};
+
+use crate::gcd_ext;
+
+/// An integer modulo an odd `MOD`, stored in Montgomery form with `R = 2^32`.
+///
+/// Values stay in Montgomery form across arithmetic operations, so each
+/// multiplication costs a single reduction. Conversion only happens in
+/// `new` and `get`.
+#[derive(Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub struct MontgomeryModInt<const MOD: u32>(u32);
+
+impl<const MOD: u32> MontgomeryModInt<MOD> {
+    /// `MOD^(-1) mod R`
+    const N_INV: u32 = {
+        assert!(MOD & 1 == 1, "MOD must be odd.");
+        // `MOD * MOD ≡ 1 (mod 8)`, so the initial value is correct in the lowest 3 bits.
+        let mut x = MOD;
+        let mut i = 0;
+        while i < 4 {
+            x = x.wrapping_mul(2u32.wrapping_sub(MOD.wrapping_mul(x)));
+            i += 1;
+        }
+        x
+    };
+    /// `R^2 mod MOD`
+    const R2: u32 = ((u64::MAX % MOD as u64 + 1) % MOD as u64) as u32;
+
+    /// Compute `(a * R^(-1)) % MOD`
+    /// when `0 <= a < MOD * R`
+    const fn reduce(a: u64) -> u32 {
+        let m = (a as u32).wrapping_mul(Self::N_INV);
+        let mn = m as u64 * MOD as u64;
+        // The lower halves of `a` and `m * MOD` are equal.
+        let (t, borrow) = ((a >> 32) as u32).overflowing_sub((mn >> 32) as u32);
+        if borrow {
+            t.wrapping_add(MOD)
+        } else {
+            t
+        }
+    }
+
+    pub const fn new(x: i64) -> Self {
+        let x = x.rem_euclid(MOD as i64) as u64;
+        Self(Self::reduce(x * Self::R2 as u64))
+    }
+
+    pub const fn get(self) -> u32 {
+        Self::reduce(self.0 as u64)
+    }
+
+    pub const fn add_const(self, rhs: Self) -> Self {
+        let sum = self.0 as u64 + rhs.0 as u64;
+        if sum >= MOD as u64 {
+            Self((sum - MOD as u64) as u32)
+        } else {
+            Self(sum as u32)
+        }
+    }
+
+    pub const fn sub_const(self, rhs: Self) -> Self {
+        let (diff, borrow) = self.0.overflowing_sub(rhs.0);
+        if borrow {
+            Self(diff.wrapping_add(MOD))
+        } else {
+            Self(diff)
+        }
+    }
+
+    pub const fn mul_const(self, rhs: Self) -> Self {
+        Self(Self::reduce(self.0 as u64 * rhs.0 as u64))
+    }
+
+    pub const fn pow(self, mut exp: u64) -> Self {
+        let mut result = Self::new(1);
+        let mut base = self;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = result.mul_const(base);
+            }
+            base = base.mul_const(base);
+            exp >>= 1;
+        }
+        result
+    }
+
+    /// Returns `None` if `self` and `MOD` are not coprime.
+    pub fn checked_inv(self) -> Option<Self> {
+        let (g, x, _) = gcd_ext(self.get() as i64, MOD as i64);
+        (g == 1).then(|| Self::new(x))
+    }
+
+    pub fn inv(self) -> Self {
+        self.checked_inv()
+            .expect("Cannot calculate the inverse of a number not coprime to the modulo.")
+    }
+}
+
+impl<const MOD: u32> From<u32> for MontgomeryModInt<MOD> {
+    fn from(value: u32) -> Self {
+        Self(Self::reduce((value % MOD) as u64 * Self::R2 as u64))
+    }
+}
+
+impl<const MOD: u32> Add for MontgomeryModInt<MOD> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.add_const(rhs)
+    }
+}
+
+impl<const MOD: u32> AddAssign for MontgomeryModInt<MOD> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const MOD: u32> Sub for MontgomeryModInt<MOD> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.sub_const(rhs)
+    }
+}
+
+impl<const MOD: u32> SubAssign for MontgomeryModInt<MOD> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<const MOD: u32> Mul for MontgomeryModInt<MOD> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.mul_const(rhs)
+    }
+}
+
+impl<const MOD: u32> MulAssign for MontgomeryModInt<MOD> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl<const MOD: u32> Div for MontgomeryModInt<MOD> {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        self.mul_const(rhs.inv())
+    }
+}
+
+impl<const MOD: u32> DivAssign for MontgomeryModInt<MOD> {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+
+impl<const MOD: u32> Debug for MontgomeryModInt<MOD> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("MontgomeryModInt").field(&self.get()).finish()
+    }
+}
+
+impl<const MOD: u32> Display for MontgomeryModInt<MOD> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.get())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic_test() {
+        type M = MontgomeryModInt<998244353>;
+        let a = M::new(-3);
+        assert_eq!(a.get(), 998244350);
+        assert_eq!((a + M::new(5)).get(), 2);
+        assert_eq!((M::new(5) - M::new(7)).get(), 998244351);
+        assert_eq!((a * a).get(), 9);
+        assert_eq!(M::new(3).pow(998244352).get(), 1);
+        assert_eq!((M::new(12345) / M::new(12345)).get(), 1);
+        assert_eq!(M::from(998244354).get(), 1);
+        assert_eq!(format!("{a} {a:?}"), "998244350 MontgomeryModInt(998244350)");
+
+        type C = MontgomeryModInt<15>;
+        assert_eq!(C::new(7).inv().get(), 13);
+        assert_eq!(C::new(5).checked_inv(), None);
+    }
+
+    #[test]
+    fn multiply_random() {
+        use rand::Rng;
+        const N: u32 = u32::MAX - 4;
+        let mut rng = rand::thread_rng();
+        for _ in 0..10000 {
+            let a = rng.gen_range(0..N);
+            let b = rng.gen_range(0..N);
+            let ma = MontgomeryModInt::<N>::from(a);
+            let mb = MontgomeryModInt::<N>::from(b);
+            assert_eq!((ma * mb).get() as u64, a as u64 * b as u64 % N as u64);
+            assert_eq!((ma + mb).get() as u64, (a as u64 + b as u64) % N as u64);
+            assert_eq!((ma - mb).get() as u64, (a as u64 + N as u64 - b as u64) % N as u64);
+        }
+    }
+}
@@ -0,0 +1,357 @@
+use crate::{Montgomery, Montgomery64};
+
+pub(crate) const R32: u64 = 1 << 32;
+
+fn pow_mod(a: u64, exp: u64, n: u64) -> u64 {
+    if n & 1 == 1 {
+        return Montgomery64::new(n).unwrap().pow(a % n, exp);
+    }
+    let mul_mod = |a: u64, b: u64| (a as u128 * b as u128 % n as u128) as u64;
+    let mut result = 1 % n;
+    let mut base = a % n;
+    let mut exp = exp;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_mod(result, base);
+        }
+        base = mul_mod(base, base);
+        exp >>= 1;
+    }
+    result
+}
+
+pub(crate) fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Deterministic Miller–Rabin test for 32-bit integers.
+///
+/// The bases `2, 7, 61` are sufficient for every `n < 2^32`.
+pub const fn is_prime_u32(n: u32) -> bool {
+    if n < 64 {
+        return (1u64 << n) & 0x28208a20a08a28ac != 0;
+    }
+    if n & 1 == 0 {
+        return false;
+    }
+    let m = match Montgomery::<R32>::new(n) {
+        Ok(m) => m,
+        Err(_) => unreachable!(),
+    };
+    let s = (n - 1).trailing_zeros();
+    let d = ((n - 1) >> s) as u64;
+    let bases = [2, 7, 61];
+    let mut i = 0;
+    'witness: while i < bases.len() {
+        let a = bases[i];
+        i += 1;
+        let mut x = m.pow(a, d);
+        if x == 1 || x == n - 1 {
+            continue;
+        }
+        let mut j = 1;
+        while j < s {
+            x = m.multiply(x, x);
+            if x == n - 1 {
+                continue 'witness;
+            }
+            j += 1;
+        }
+        return false;
+    }
+    true
+}
+
+/// Deterministic Miller–Rabin test for 64-bit integers.
+///
+/// Uses the seven bases found by Jim Sinclair, which are sufficient for every `n < 2^64`.
+pub const fn is_prime_u64(n: u64) -> bool {
+    if n <= u32::MAX as u64 {
+        return is_prime_u32(n as u32);
+    }
+    if n & 1 == 0 {
+        return false;
+    }
+    let m = match Montgomery64::new(n) {
+        Ok(m) => m,
+        Err(_) => unreachable!(),
+    };
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+    let bases = [2, 325, 9375, 28178, 450775, 9780504, 1795265022];
+    let mut i = 0;
+    'witness: while i < bases.len() {
+        let a = bases[i] % n;
+        i += 1;
+        if a == 0 {
+            continue;
+        }
+        let mut x = m.pow(a, d);
+        if x == 1 || x == n - 1 {
+            continue;
+        }
+        let mut j = 1;
+        while j < s {
+            x = m.multiply(x, x);
+            if x == n - 1 {
+                continue 'witness;
+            }
+            j += 1;
+        }
+        return false;
+    }
+    true
+}
+
+/// Find a nontrivial factor of an odd composite `n` with Brent's variant of Pollard's rho.
+///
+/// `mul` must compute a product modulo `n` up to a constant factor coprime to `n`,
+/// e.g. a Montgomery reduction.
+fn pollard_brent(n: u64, mul: impl Fn(u64, u64) -> u64) -> u64 {
+    const BLOCK: u64 = 128;
+    let add = |a: u64, c: u64| if a >= n - c { a - (n - c) } else { a + c };
+    for c in 1..n {
+        let f = |x: u64| add(mul(x, x), c);
+        let (mut x, mut y, mut ys) = (0, 2, 2);
+        let (mut q, mut g, mut r) = (1, 1, 1);
+        while g == 1 {
+            x = y;
+            for _ in 0..r {
+                y = f(y);
+            }
+            let mut k = 0;
+            while k < r && g == 1 {
+                ys = y;
+                for _ in 0..BLOCK.min(r - k) {
+                    y = f(y);
+                    q = mul(q, x.abs_diff(y));
+                }
+                g = gcd(q, n);
+                k += BLOCK;
+            }
+            r <<= 1;
+        }
+        if g == n {
+            // The block overshot; retrace it one step at a time.
+            loop {
+                ys = f(ys);
+                g = gcd(x.abs_diff(ys), n);
+                if g != 1 {
+                    break;
+                }
+            }
+        }
+        if g != n {
+            return g;
+        }
+    }
+    unreachable!("{n} must be an odd composite number")
+}
+
+fn find_factor(n: u64) -> u64 {
+    if n < R32 {
+        let m = Montgomery::<R32>::new(n as u32).unwrap();
+        pollard_brent(n, |a, b| m.reduce(a * b) as u64)
+    } else {
+        let m = Montgomery64::new(n).unwrap();
+        pollard_brent(n, |a, b| m.reduce(a as u128 * b as u128))
+    }
+}
+
+/// Returns the prime factors of `n` in ascending order, repeated by multiplicity.
+///
+/// Returns an empty vector for `n <= 1`.
+pub fn factorize(mut n: u64) -> Vec<u64> {
+    let mut factors = Vec::new();
+    if n <= 1 {
+        return factors;
+    }
+    for p in [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37] {
+        while n.is_multiple_of(p) {
+            factors.push(p);
+            n /= p;
+        }
+    }
+    let mut stack = if n > 1 { vec![n] } else { Vec::new() };
+    while let Some(m) = stack.pop() {
+        if is_prime_u64(m) {
+            factors.push(m);
+        } else {
+            let d = find_factor(m);
+            stack.push(d);
+            stack.push(m / d);
+        }
+    }
+    factors.sort_unstable();
+    factors
+}
+
+/// Returns pairs of a prime factor and its exponent in ascending order of the prime.
+fn factorize_grouped(n: u64) -> Vec<(u64, u32)> {
+    let mut grouped: Vec<(u64, u32)> = Vec::new();
+    for p in factorize(n) {
+        match grouped.last_mut() {
+            Some((q, e)) if *q == p => *e += 1,
+            _ => grouped.push((p, 1)),
+        }
+    }
+    grouped
+}
+
+/// Returns all positive divisors of `n` in ascending order.
+///
+/// Returns an empty vector for `n == 0`.
+pub fn divisors(n: u64) -> Vec<u64> {
+    if n == 0 {
+        return Vec::new();
+    }
+    let mut divisors = vec![1];
+    for (p, e) in factorize_grouped(n) {
+        let len = divisors.len();
+        let mut pk = 1;
+        for _ in 0..e {
+            pk *= p;
+            for i in 0..len {
+                divisors.push(divisors[i] * pk);
+            }
+        }
+    }
+    divisors.sort_unstable();
+    divisors
+}
+
+/// Euler's totient function, the number of integers in `1..=n` coprime to `n`.
+pub fn euler_phi(n: u64) -> u64 {
+    factorize_grouped(n)
+        .into_iter()
+        .fold(n, |phi, (p, _)| phi / p * (p - 1))
+}
+
+/// Returns the smallest primitive root modulo `n`, or `None` if there is none.
+///
+/// A primitive root exists only if `n` is `1`, `2`, `4`, `p^k` or `2p^k` for an odd prime `p`.
+pub fn primitive_root(n: u64) -> Option<u64> {
+    match n {
+        0 => return None,
+        1 => return Some(0),
+        2 => return Some(1),
+        4 => return Some(3),
+        _ => {}
+    }
+    let odd = if n.is_multiple_of(2) { n / 2 } else { n };
+    let grouped = factorize_grouped(odd);
+    if odd.is_multiple_of(2) || grouped.len() != 1 {
+        return None;
+    }
+    let phi = euler_phi(n);
+    let phi_factors = factorize_grouped(phi);
+    (2..n).find(|&g| {
+        gcd(g, n) == 1
+            && phi_factors
+                .iter()
+                .all(|&(q, _)| pow_mod(g, phi / q, n) != 1)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    fn is_prime_naive(n: u64) -> bool {
+        n >= 2 && (2..).take_while(|i| i * i <= n).all(|i| !n.is_multiple_of(i))
+    }
+
+    #[test]
+    fn is_prime_small() {
+        for n in 0..100000u32 {
+            assert_eq!(is_prime_u32(n), is_prime_naive(n as u64), "{n}");
+            assert_eq!(is_prime_u64(n as u64), is_prime_naive(n as u64), "{n}");
+        }
+    }
+
+    #[test]
+    fn is_prime_large() {
+        const _: () = assert!(is_prime_u32(998244353));
+        for p in [998244353, 1000000007, 4294967291] {
+            assert!(is_prime_u32(p));
+            assert!(is_prime_u64(p as u64));
+        }
+        // strong pseudoprimes to several small bases
+        for n in [3215031751, 4759123141, 1122004669633, 3825123056546413051] {
+            assert!(!is_prime_u64(n), "{n}");
+        }
+        assert!(!is_prime_u32(3215031751));
+        assert!(!is_prime_u32(u32::MAX));
+        for p in [(1 << 61) - 1, 18446744073709551557, 4611686018427387847] {
+            assert!(is_prime_u64(p), "{p}");
+        }
+        assert!(!is_prime_u64(u64::MAX));
+        assert!(!is_prime_u64(4294967291 * 4294967279));
+    }
+
+    #[test]
+    fn factorize_test() {
+        assert_eq!(factorize(0), vec![]);
+        assert_eq!(factorize(1), vec![]);
+        assert_eq!(factorize(360), vec![2, 2, 2, 3, 3, 5]);
+        assert_eq!(
+            factorize(4294967291 * 4294967279),
+            vec![4294967279, 4294967291]
+        );
+        assert_eq!(
+            factorize(1000000007 * 998244353),
+            vec![998244353, 1000000007]
+        );
+        assert_eq!(factorize(41 * 41 * 43), vec![41, 41, 43]);
+        assert_eq!(
+            factorize(u64::MAX),
+            vec![3, 5, 17, 257, 641, 65537, 6700417]
+        );
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let n = rng.gen::<u64>();
+            let factors = factorize(n);
+            assert_eq!(factors.iter().product::<u64>(), n);
+            assert!(factors.iter().all(|&p| is_prime_u64(p)));
+            assert!(factors.windows(2).all(|w| w[0] <= w[1]));
+        }
+    }
+
+    #[test]
+    fn divisors_test() {
+        assert_eq!(divisors(0), vec![]);
+        assert_eq!(divisors(1), vec![1]);
+        assert_eq!(divisors(12), vec![1, 2, 3, 4, 6, 12]);
+        for n in 1..500u64 {
+            let expected = (1..=n).filter(|&d| n.is_multiple_of(d)).collect::<Vec<_>>();
+            assert_eq!(divisors(n), expected);
+        }
+    }
+
+    #[test]
+    fn euler_phi_test() {
+        assert_eq!(euler_phi(1), 1);
+        for n in 1..500 {
+            let expected = (1..=n).filter(|&k| gcd(k, n) == 1).count() as u64;
+            assert_eq!(euler_phi(n), expected);
+        }
+        assert_eq!(euler_phi(998244353), 998244352);
+    }
+
+    #[test]
+    fn primitive_root_test() {
+        assert_eq!(primitive_root(998244353), Some(3));
+        assert_eq!(primitive_root(1000000007), Some(5));
+        for n in 2..300u64 {
+            let phi = euler_phi(n);
+            let expected = (0..n).find(|&g| {
+                gcd(g, n) == 1 && (1..=phi).find(|&k| pow_mod(g, k, n) == 1) == Some(phi)
+            });
+            assert_eq!(primitive_root(n), expected, "{n}");
+        }
+    }
+}
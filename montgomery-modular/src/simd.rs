@@ -0,0 +1,185 @@
+//! AVX2 implementations of the batched operations of [`Montgomery`].
+//!
+//! Each 256-bit vector holds four residues in its 64-bit lanes, so that
+//! `_mm256_mul_epu32` yields the full 64-bit products.
+
+use std::arch::x86_64::*;
+
+use crate::Montgomery;
+
+const LANES: usize = 4;
+
+struct Consts {
+    one: __m256i,
+    n: __m256i,
+    n_prime: __m256i,
+    r2: __m256i,
+    mask: __m256i,
+    shift: __m128i,
+}
+
+impl Consts {
+    #[target_feature(enable = "avx2")]
+    fn new<const R: u64>(m: &Montgomery<R>) -> Self {
+        Self {
+            one: _mm256_set1_epi64x((1 % m.n) as i64),
+            n: _mm256_set1_epi64x(m.n as i64),
+            n_prime: _mm256_set1_epi64x(m.n_prime as i64),
+            r2: _mm256_set1_epi64x(m.r2 as i64),
+            mask: _mm256_set1_epi64x(Montgomery::<R>::MASK as i64),
+            shift: _mm_set_epi64x(0, Montgomery::<R>::SHIFT as i64),
+        }
+    }
+
+    /// Vectorized [`Montgomery::reduce`].
+    #[target_feature(enable = "avx2")]
+    fn reduce(&self, a: __m256i) -> __m256i {
+        let m = _mm256_and_si256(_mm256_mul_epu32(a, self.n_prime), self.mask);
+        let mn = _mm256_mul_epu32(m, self.n);
+        let hi = _mm256_srl_epi64(a, self.shift);
+        let lo = _mm256_srl_epi64(
+            _mm256_add_epi64(_mm256_and_si256(a, self.mask), mn),
+            self.shift,
+        );
+        let t = _mm256_add_epi64(hi, lo);
+        // `t < 2n < 2^33`, so the signed comparison is correct.
+        let lt = _mm256_cmpgt_epi64(self.n, t);
+        _mm256_sub_epi64(t, _mm256_andnot_si256(lt, self.n))
+    }
+
+    /// Vectorized [`Montgomery::multiply`].
+    #[target_feature(enable = "avx2")]
+    fn multiply(&self, a: __m256i, b: __m256i) -> __m256i {
+        let c = self.reduce(_mm256_mul_epu32(a, b));
+        self.reduce(_mm256_mul_epu32(c, self.r2))
+    }
+
+    /// Vectorized [`Montgomery::pow`].
+    #[target_feature(enable = "avx2")]
+    fn pow(&self, a: __m256i, mut exp: u64) -> __m256i {
+        let mut result = self.one;
+        let mut base = self.reduce(_mm256_mul_epu32(a, self.r2));
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = self.reduce(_mm256_mul_epu32(result, base));
+            }
+            base = self.reduce(_mm256_mul_epu32(base, base));
+            exp >>= 1;
+        }
+        result
+    }
+}
+
+/// Load four `u32` into the 64-bit lanes.
+#[target_feature(enable = "avx2")]
+fn load_u32(a: &[u32]) -> __m256i {
+    assert!(a.len() >= LANES);
+    // SAFETY: `a` has at least 16 bytes and `_mm_loadu_si128` allows unaligned pointers.
+    _mm256_cvtepu32_epi64(unsafe { _mm_loadu_si128(a.as_ptr().cast()) })
+}
+
+/// Store the lower halves of the 64-bit lanes into four `u32`.
+#[target_feature(enable = "avx2")]
+fn store_u32(a: &mut [u32], v: __m256i) {
+    assert!(a.len() >= LANES);
+    let packed = _mm256_permutevar8x32_epi32(v, _mm256_setr_epi32(0, 2, 4, 6, 0, 0, 0, 0));
+    // SAFETY: `a` has at least 16 bytes and `_mm_storeu_si128` allows unaligned pointers.
+    unsafe { _mm_storeu_si128(a.as_mut_ptr().cast(), _mm256_castsi256_si128(packed)) };
+}
+
+#[target_feature(enable = "avx2")]
+pub(crate) fn multiply_slice<const R: u64>(m: &Montgomery<R>, a: &mut [u32], b: &[u32]) {
+    let c = Consts::new(m);
+    let mut a_chunks = a.chunks_exact_mut(LANES);
+    let mut b_chunks = b.chunks_exact(LANES);
+    for (x, y) in a_chunks.by_ref().zip(b_chunks.by_ref()) {
+        store_u32(x, c.multiply(load_u32(x), load_u32(y)));
+    }
+    for (x, &y) in a_chunks
+        .into_remainder()
+        .iter_mut()
+        .zip(b_chunks.remainder())
+    {
+        *x = m.multiply(*x, y);
+    }
+}
+
+#[target_feature(enable = "avx2")]
+pub(crate) fn pow_slice<const R: u64>(m: &Montgomery<R>, a: &mut [u32], exp: u64) {
+    let c = Consts::new(m);
+    let mut chunks = a.chunks_exact_mut(LANES);
+    for x in chunks.by_ref() {
+        store_u32(x, c.pow(load_u32(x), exp));
+    }
+    for x in chunks.into_remainder() {
+        *x = m.pow(*x, exp);
+    }
+}
+
+#[target_feature(enable = "avx2")]
+pub(crate) fn reduce_slice<const R: u64>(m: &Montgomery<R>, a: &[u64], out: &mut [u32]) {
+    let c = Consts::new(m);
+    let mut a_chunks = a.chunks_exact(LANES);
+    let mut out_chunks = out.chunks_exact_mut(LANES);
+    for (x, y) in a_chunks.by_ref().zip(out_chunks.by_ref()) {
+        // SAFETY: `x` has exactly 32 bytes and `_mm256_loadu_si256` allows unaligned pointers.
+        let v = unsafe { _mm256_loadu_si256(x.as_ptr().cast()) };
+        store_u32(y, c.reduce(v));
+    }
+    for (&x, y) in a_chunks.remainder().iter().zip(out_chunks.into_remainder()) {
+        *y = m.reduce(x);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    fn check<const R: u64>(n: u32) {
+        if !is_x86_feature_detected!("avx2") {
+            return;
+        }
+        let m = Montgomery::<R>::new(n).unwrap();
+        let mut rng = rand::thread_rng();
+        for len in [0, 1, 3, 4, 5, 8, 100] {
+            let a = (0..len).map(|_| rng.gen_range(0..n)).collect::<Vec<_>>();
+            let b = (0..len).map(|_| rng.gen_range(0..n)).collect::<Vec<_>>();
+
+            let mut c = a.clone();
+            // SAFETY: AVX2 is available.
+            unsafe { multiply_slice(&m, &mut c, &b) };
+            for i in 0..len {
+                assert_eq!(c[i], m.multiply(a[i], b[i]));
+            }
+
+            for exp in [0, 1, 2, 12345, u64::MAX] {
+                let mut c = a.clone();
+                // SAFETY: AVX2 is available.
+                unsafe { pow_slice(&m, &mut c, exp) };
+                for i in 0..len {
+                    assert_eq!(c[i], m.pow(a[i], exp));
+                }
+            }
+
+            let wide = (0..len)
+                .map(|_| rng.gen_range(0..n as u64 * R))
+                .collect::<Vec<_>>();
+            let mut c = vec![0; len];
+            // SAFETY: AVX2 is available.
+            unsafe { reduce_slice(&m, &wide, &mut c) };
+            for i in 0..len {
+                assert_eq!(c[i], m.reduce(wide[i]));
+            }
+        }
+    }
+
+    #[test]
+    fn avx2_matches_scalar() {
+        check::<256>(1);
+        check::<256>(167);
+        check::<{ 1 << 16 }>(65521);
+        check::<{ 1 << 32 }>(998244353);
+        check::<{ 1 << 32 }>(u32::MAX - 4);
+    }
+}
@@ -0,0 +1,382 @@
+use std::{
+    cmp::Ordering,
+    fmt::{self, Display},
+    iter::{Product, Sum},
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign},
+    str::FromStr,
+};
+
+use crate::{
+    biguint::{forward_binop, BigUint, ParseBigIntError},
+    traits::{Arithmetic, ExistsBiggerInt, Integer, Signed},
+};
+
+/// 任意精度の符号付き整数。
+///
+/// 符号と絶対値で表す。`0`は常に非負として持つ。
+/// 除算と剰余はプリミティブ型と同じく0方向への切り捨てで、剰余の符号は被除数と一致する。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct BigInt {
+    negative: bool,
+    magnitude: BigUint,
+}
+
+impl BigInt {
+    /// 符号と絶対値から作る。`magnitude`が`0`の場合は`negative`を無視する。
+    pub fn from_parts(negative: bool, magnitude: BigUint) -> Self {
+        Self {
+            negative: negative && !magnitude.is_zero(),
+            magnitude,
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.magnitude.is_zero()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    /// 絶対値
+    pub fn magnitude(&self) -> &BigUint {
+        &self.magnitude
+    }
+
+    /// 符号と絶対値に分解する。
+    pub fn into_parts(self) -> (bool, BigUint) {
+        (self.negative, self.magnitude)
+    }
+
+    /// 0方向に切り捨てた商と余りを同時に求める。
+    pub fn div_rem(&self, rhs: &Self) -> (Self, Self) {
+        let (q, r) = self.magnitude.div_rem(&rhs.magnitude);
+        (
+            Self::from_parts(self.negative != rhs.negative, q),
+            Self::from_parts(self.negative, r),
+        )
+    }
+
+    /// `radix`進数の文字列から変換する。先頭に符号を付けてもよい。
+    pub fn from_str_radix(s: &str, radix: u32) -> Result<Self, ParseBigIntError> {
+        let (negative, s) = match s.strip_prefix('-') {
+            Some(s) => (true, s),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+        if s.starts_with(['+', '-']) {
+            return Err(ParseBigIntError::InvalidDigit);
+        }
+        Ok(Self::from_parts(
+            negative,
+            BigUint::from_str_radix(s, radix)?,
+        ))
+    }
+
+    /// `radix`進数の文字列に変換する。負の場合は先頭に`-`が付く。
+    pub fn to_str_radix(&self, radix: u32) -> String {
+        let digits = self.magnitude.to_str_radix(radix);
+        if self.negative {
+            format!("-{digits}")
+        } else {
+            digits
+        }
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, false) => self.magnitude.cmp(&other.magnitude),
+            (true, true) => other.magnitude.cmp(&self.magnitude),
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+        }
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl From<BigUint> for BigInt {
+    fn from(value: BigUint) -> Self {
+        Self::from_parts(false, value)
+    }
+}
+
+macro_rules! impl_from_signed {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for BigInt {
+                fn from(value: $t) -> Self {
+                    Self::from_parts(value < 0, BigUint::from(value.unsigned_abs()))
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_from_unsigned {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for BigInt {
+                fn from(value: $t) -> Self {
+                    Self::from_parts(false, BigUint::from(value))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_signed!(i8, i16, i32, i64, i128, isize);
+impl_from_unsigned!(u8, u16, u32, u64, u128, usize);
+
+impl Neg for BigInt {
+    type Output = BigInt;
+    fn neg(self) -> BigInt {
+        Self::from_parts(!self.negative, self.magnitude)
+    }
+}
+
+impl Neg for &BigInt {
+    type Output = BigInt;
+    fn neg(self) -> BigInt {
+        -self.clone()
+    }
+}
+
+impl<'a> Add<&'a BigInt> for &BigInt {
+    type Output = BigInt;
+    fn add(self, rhs: &'a BigInt) -> BigInt {
+        if self.negative == rhs.negative {
+            return BigInt::from_parts(self.negative, &self.magnitude + &rhs.magnitude);
+        }
+        // 符号が異なる場合は絶対値の大きい方から小さい方を引く
+        if self.magnitude >= rhs.magnitude {
+            BigInt::from_parts(self.negative, &self.magnitude - &rhs.magnitude)
+        } else {
+            BigInt::from_parts(rhs.negative, &rhs.magnitude - &self.magnitude)
+        }
+    }
+}
+
+impl<'a> Sub<&'a BigInt> for &BigInt {
+    type Output = BigInt;
+    fn sub(self, rhs: &'a BigInt) -> BigInt {
+        self + &-rhs
+    }
+}
+
+impl<'a> Mul<&'a BigInt> for &BigInt {
+    type Output = BigInt;
+    fn mul(self, rhs: &'a BigInt) -> BigInt {
+        BigInt::from_parts(
+            self.negative != rhs.negative,
+            &self.magnitude * &rhs.magnitude,
+        )
+    }
+}
+
+impl<'a> Div<&'a BigInt> for &BigInt {
+    type Output = BigInt;
+    fn div(self, rhs: &'a BigInt) -> BigInt {
+        self.div_rem(rhs).0
+    }
+}
+
+impl<'a> Rem<&'a BigInt> for &BigInt {
+    type Output = BigInt;
+    fn rem(self, rhs: &'a BigInt) -> BigInt {
+        self.div_rem(rhs).1
+    }
+}
+
+forward_binop!(
+    BigInt,
+    Add, add, AddAssign, add_assign;
+    Sub, sub, SubAssign, sub_assign;
+    Mul, mul, MulAssign, mul_assign;
+    Div, div, DivAssign, div_assign;
+    Rem, rem, RemAssign, rem_assign
+);
+
+impl Sum for BigInt {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), |acc, x| acc + x)
+    }
+}
+
+impl Product for BigInt {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::one(), |acc, x| acc * x)
+    }
+}
+
+impl Arithmetic for BigInt {
+    type BitWidthType = u32;
+    fn pow(self, exp: u32) -> Self {
+        let negative = self.negative && exp % 2 == 1;
+        Self::from_parts(negative, self.magnitude.pow(exp))
+    }
+
+    fn div_euclid(self, rhs: Self) -> Self {
+        let (q, r) = self.div_rem(&rhs);
+        if r.negative {
+            if rhs.negative {
+                q + Self::one()
+            } else {
+                q - Self::one()
+            }
+        } else {
+            q
+        }
+    }
+
+    fn rem_euclid(self, rhs: Self) -> Self {
+        let r = &self % &rhs;
+        if r.negative {
+            r + rhs.abs()
+        } else {
+            r
+        }
+    }
+}
+
+impl Integer for BigInt {
+    fn zero() -> Self {
+        Self::default()
+    }
+
+    fn one() -> Self {
+        Self::from(1u32)
+    }
+}
+
+impl Signed for BigInt {
+    fn abs(self) -> Self {
+        Self::from_parts(false, self.magnitude)
+    }
+
+    fn signum(self) -> Self {
+        match (self.negative, self.is_zero()) {
+            (true, _) => -Self::one(),
+            (false, true) => Self::zero(),
+            (false, false) => Self::one(),
+        }
+    }
+}
+
+/// `i128`の2倍幅として使う。`cast_from_twice`は2の補数表現の下位128bitを返す。
+impl ExistsBiggerInt for i128 {
+    type Twice = BigInt;
+    fn cast_to_twice(self) -> BigInt {
+        BigInt::from(self)
+    }
+
+    fn cast_from_twice(twice: BigInt) -> Self {
+        let low = twice.magnitude.low_u128();
+        if twice.negative {
+            low.wrapping_neg() as i128
+        } else {
+            low as i128
+        }
+    }
+}
+
+impl FromStr for BigInt {
+    type Err = ParseBigIntError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_str_radix(s, 10)
+    }
+}
+
+impl Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad_integral(!self.negative, "", &self.magnitude.to_str_radix(10))
+    }
+}
+
+impl fmt::LowerHex for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad_integral(!self.negative, "0x", &self.magnitude.to_str_radix(16))
+    }
+}
+
+impl fmt::UpperHex for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = self.magnitude.to_str_radix(16).to_uppercase();
+        f.pad_integral(!self.negative, "0x", &digits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{gcd, gcd_ext, mod_inverse};
+    use rand::Rng;
+
+    #[test]
+    fn arithmetic_test() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..1000 {
+            let a = rng.gen::<i64>() as i128;
+            let b = rng.gen::<i64>() as i128 >> rng.gen_range(0..64);
+            let (x, y) = (BigInt::from(a), BigInt::from(b));
+            assert_eq!(&x + &y, BigInt::from(a + b));
+            assert_eq!(&x - &y, BigInt::from(a - b));
+            assert_eq!(&x * &y, BigInt::from(a * b));
+            assert_eq!(-&x, BigInt::from(-a));
+            assert_eq!(x.cmp(&y), a.cmp(&b));
+            if b != 0 {
+                assert_eq!(&x / &y, BigInt::from(a / b));
+                assert_eq!(&x % &y, BigInt::from(a % b));
+                assert_eq!(
+                    x.clone().div_euclid(y.clone()),
+                    BigInt::from(a.div_euclid(b))
+                );
+                assert_eq!(x.rem_euclid(y), BigInt::from(a.rem_euclid(b)));
+            }
+        }
+        assert_eq!(BigInt::from(-3).pow(3), BigInt::from(-27));
+        assert_eq!(BigInt::from(-3).pow(0), BigInt::one());
+        assert_eq!(BigInt::from(-3).abs(), BigInt::from(3));
+        assert_eq!(BigInt::from(-3).signum(), BigInt::from(-1));
+        assert_eq!(BigInt::zero().signum(), BigInt::zero());
+        assert_eq!(BigInt::from(5) - BigInt::from(5), BigInt::zero());
+        assert!(!(BigInt::from(-5) + BigInt::from(5)).is_negative());
+        assert_eq!(i128::cast_from_twice(i128::MIN.cast_to_twice()), i128::MIN);
+        assert_eq!(i128::cast_from_twice(BigInt::from(-7)), -7);
+    }
+
+    #[test]
+    fn parse_and_format_test() {
+        let s = "-265252859812191058636308480000000";
+        let x: BigInt = s.parse().unwrap();
+        assert_eq!(x, -(1..=30).map(BigInt::from).product::<BigInt>());
+        assert_eq!(x.to_string(), s);
+        assert_eq!(format!("{:x}", BigInt::from(-255)), "-ff");
+        assert_eq!(format!("{:#X}", BigInt::from(-255)), "-0xFF");
+        assert_eq!(format!("{:+}", BigInt::from(42)), "+42");
+        assert_eq!(BigInt::from(-255).to_str_radix(2), "-11111111");
+        assert_eq!(BigInt::from_str_radix("-ff", 16), Ok(BigInt::from(-255)));
+        assert_eq!("-0".parse::<BigInt>(), Ok(BigInt::zero()));
+        assert_eq!("+12".parse::<BigInt>(), Ok(BigInt::from(12)));
+        assert_eq!("-".parse::<BigInt>(), Err(ParseBigIntError::Empty));
+        assert_eq!("--1".parse::<BigInt>(), Err(ParseBigIntError::InvalidDigit));
+        assert_eq!("-+1".parse::<BigInt>(), Err(ParseBigIntError::InvalidDigit));
+    }
+
+    #[test]
+    fn generic_test() {
+        let a = BigInt::from(2).pow(127) - BigInt::one();
+        let b = BigInt::from(2).pow(89) - BigInt::one();
+        assert_eq!(gcd(a.clone(), b.clone()), BigInt::one());
+        let (g, x, y) = gcd_ext(a.clone(), b.clone());
+        assert_eq!(g, BigInt::one());
+        assert_eq!(&a * &x + &b * &y, g);
+        let inv = mod_inverse(b.clone(), a.clone()).unwrap();
+        assert_eq!(inv * b % a, BigInt::one());
+    }
+}
@@ -0,0 +1,759 @@
+use std::{
+    cmp::Ordering,
+    error::Error,
+    fmt::{self, Display},
+    iter::{Product, Sum},
+    ops::{
+        Add, AddAssign, Div, DivAssign, Mul, MulAssign, Rem, RemAssign, Shl, ShlAssign, Shr,
+        ShrAssign, Sub, SubAssign,
+    },
+    str::FromStr,
+};
+
+use crate::traits::{Arithmetic, ExistsBiggerInt, Integer, Unsigned};
+
+/// この長さ未満の乗算は筆算で行う。
+const KARATSUBA_THRESHOLD: usize = 32;
+
+/// 多倍長整数の文字列からの変換に失敗したときのエラー
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseBigIntError {
+    /// 数字が1つも含まれていない
+    Empty,
+    /// 基数に対して不正な文字が含まれている
+    InvalidDigit,
+}
+
+impl Display for ParseBigIntError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "cannot parse integer from empty string"),
+            Self::InvalidDigit => write!(f, "invalid digit found in string"),
+        }
+    }
+}
+
+impl Error for ParseBigIntError {}
+
+/// 任意精度の非負整数。
+///
+/// 32bitのリムを下位から並べて持つ。最上位のリムは常に非零で、`0`は空の列で表す。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct BigUint {
+    limbs: Vec<u32>,
+}
+
+/// 末尾(上位)の0のリムを取り除く。
+fn trim(limbs: &mut Vec<u32>) {
+    while limbs.last() == Some(&0) {
+        limbs.pop();
+    }
+}
+
+fn cmp_limbs(a: &[u32], b: &[u32]) -> Ordering {
+    a.len()
+        .cmp(&b.len())
+        .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+/// `a[offset..] += b`。繰り上がりは`a`の範囲に収まる必要がある。
+fn add_at(a: &mut [u32], b: &[u32], offset: usize) {
+    let mut carry = 0;
+    let mut i = offset;
+    for &x in b {
+        let s = a[i] as u64 + x as u64 + carry;
+        a[i] = s as u32;
+        carry = s >> 32;
+        i += 1;
+    }
+    while carry != 0 {
+        let s = a[i] as u64 + carry;
+        a[i] = s as u32;
+        carry = s >> 32;
+        i += 1;
+    }
+}
+
+/// `a -= b`。`a >= b`である必要がある。
+fn sub_assign_limbs(a: &mut [u32], b: &[u32]) {
+    let mut borrow = false;
+    for (i, x) in a.iter_mut().enumerate() {
+        if i >= b.len() && !borrow {
+            break;
+        }
+        let y = b.get(i).copied().unwrap_or(0);
+        let (d, b1) = x.overflowing_sub(y);
+        let (d, b2) = d.overflowing_sub(borrow as u32);
+        *x = d;
+        borrow = b1 || b2;
+    }
+    assert!(!borrow, "attempt to subtract with overflow");
+}
+
+fn add_limbs(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let (a, b) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let mut result = Vec::with_capacity(a.len() + 1);
+    result.extend_from_slice(a);
+    result.push(0);
+    add_at(&mut result, b, 0);
+    result
+}
+
+fn mul_schoolbook(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut result = vec![0u32; a.len() + b.len()];
+    for (i, &x) in a.iter().enumerate() {
+        let mut carry = 0u64;
+        for (j, &y) in b.iter().enumerate() {
+            let t = x as u64 * y as u64 + result[i + j] as u64 + carry;
+            result[i + j] = t as u32;
+            carry = t >> 32;
+        }
+        result[i + b.len()] = carry as u32;
+    }
+    result
+}
+
+/// Karatsuba法による乗算。結果の長さは`a.len() + b.len()`で、上位に0が残ることがある。
+fn mul_limbs(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let (a, b) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    if b.len() < KARATSUBA_THRESHOLD {
+        return mul_schoolbook(a, b);
+    }
+    let mut result = vec![0u32; a.len() + b.len()];
+    if b.len() * 2 <= a.len() {
+        // 長さが大きく違う場合は長い方を`b`の長さごとに区切って掛ける
+        for (i, chunk) in a.chunks(b.len()).enumerate() {
+            let p = mul_limbs(chunk, b);
+            add_at(&mut result, trimmed(&p), i * b.len());
+        }
+        return result;
+    }
+    // a = a1 * B^m + a0, b = b1 * B^m + b0
+    // a * b = z2 * B^2m + (z1 - z2 - z0) * B^m + z0
+    let m = a.len() / 2;
+    let (a0, a1) = a.split_at(m);
+    let (b0, b1) = b.split_at(m);
+    let z0 = mul_limbs(a0, b0);
+    let z2 = mul_limbs(a1, b1);
+    let mut z1 = mul_limbs(&add_limbs(a0, a1), &add_limbs(b0, b1));
+    sub_assign_limbs(&mut z1, &z0);
+    sub_assign_limbs(&mut z1, &z2);
+    add_at(&mut result, trimmed(&z0), 0);
+    add_at(&mut result, trimmed(&z1), m);
+    add_at(&mut result, trimmed(&z2), 2 * m);
+    result
+}
+
+fn trimmed(a: &[u32]) -> &[u32] {
+    let len = a.iter().rposition(|&x| x != 0).map_or(0, |i| i + 1);
+    &a[..len]
+}
+
+/// `a / d`と`a % d`
+fn div_rem_small(a: &[u32], d: u32) -> (Vec<u32>, u32) {
+    assert!(d != 0, "attempt to divide by zero");
+    let mut q = vec![0u32; a.len()];
+    let mut r = 0u64;
+    for i in (0..a.len()).rev() {
+        let cur = r << 32 | a[i] as u64;
+        q[i] = (cur / d as u64) as u32;
+        r = cur % d as u64;
+    }
+    trim(&mut q);
+    (q, r as u32)
+}
+
+/// Knuthのアルゴリズム Dによる`u / v`と`u % v`。`v`は2リム以上で`u >= v`である必要がある。
+fn div_rem_knuth(u: &[u32], v: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    let n = v.len();
+    // 除数の最上位ビットが立つように正規化する
+    let s = v[n - 1].leading_zeros();
+    let v = shl_limbs(v, s);
+    let mut u = shl_limbs(u, s);
+    u.push(0);
+    let (v_hi, v_lo) = (v[n - 1] as u64, v[n - 2] as u64);
+    let mut q = vec![0u32; u.len() - n];
+    for j in (0..u.len() - n).rev() {
+        let num = (u[j + n] as u64) << 32 | u[j + n - 1] as u64;
+        let mut q_hat = num / v_hi;
+        let mut r_hat = num % v_hi;
+        while q_hat > u32::MAX as u64 || q_hat * v_lo > (r_hat << 32 | u[j + n - 2] as u64) {
+            q_hat -= 1;
+            r_hat += v_hi;
+            if r_hat > u32::MAX as u64 {
+                break;
+            }
+        }
+        // u[j..=j + n] -= q_hat * v
+        let mut borrow = 0i64;
+        let mut carry = 0u64;
+        for i in 0..n {
+            let p = q_hat * v[i] as u64 + carry;
+            carry = p >> 32;
+            let t = u[i + j] as i64 - borrow - (p & u32::MAX as u64) as i64;
+            u[i + j] = t as u32;
+            borrow = (t < 0) as i64;
+        }
+        let t = u[j + n] as i64 - borrow - carry as i64;
+        u[j + n] = t as u32;
+        if t < 0 {
+            // 引きすぎたので1回分足し戻す
+            q_hat -= 1;
+            let mut carry = 0u64;
+            for i in 0..n {
+                let s = u[i + j] as u64 + v[i] as u64 + carry;
+                u[i + j] = s as u32;
+                carry = s >> 32;
+            }
+            u[j + n] = u[j + n].wrapping_add(carry as u32);
+        }
+        q[j] = q_hat as u32;
+    }
+    trim(&mut q);
+    u.truncate(n);
+    let mut r = shr_limbs(&u, s);
+    trim(&mut r);
+    (q, r)
+}
+
+fn shl_limbs(a: &[u32], bits: u32) -> Vec<u32> {
+    let (words, bits) = ((bits / 32) as usize, bits % 32);
+    let mut result = vec![0u32; words];
+    if bits == 0 {
+        result.extend_from_slice(a);
+    } else {
+        let mut carry = 0;
+        for &x in a {
+            result.push(x << bits | carry);
+            carry = x >> (32 - bits);
+        }
+        result.push(carry);
+    }
+    trim(&mut result);
+    result
+}
+
+fn shr_limbs(a: &[u32], bits: u32) -> Vec<u32> {
+    let (words, bits) = ((bits / 32) as usize, bits % 32);
+    if words >= a.len() {
+        return Vec::new();
+    }
+    let a = &a[words..];
+    let mut result = if bits == 0 {
+        a.to_vec()
+    } else {
+        (0..a.len())
+            .map(|i| a[i] >> bits | a.get(i + 1).map_or(0, |&x| x << (32 - bits)))
+            .collect()
+    };
+    trim(&mut result);
+    result
+}
+
+impl BigUint {
+    fn from_limbs(mut limbs: Vec<u32>) -> Self {
+        trim(&mut limbs);
+        Self { limbs }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    /// 2進数で表したときの桁数。`0`の場合は`0`
+    pub fn bits(&self) -> u64 {
+        self.limbs.last().map_or(0, |&x| {
+            self.limbs.len() as u64 * 32 - x.leading_zeros() as u64
+        })
+    }
+
+    /// 商と余りを同時に求める。
+    pub fn div_rem(&self, rhs: &Self) -> (Self, Self) {
+        assert!(!rhs.is_zero(), "attempt to divide by zero");
+        if cmp_limbs(&self.limbs, &rhs.limbs) == Ordering::Less {
+            return (Self::default(), self.clone());
+        }
+        if rhs.limbs.len() == 1 {
+            let (q, r) = div_rem_small(&self.limbs, rhs.limbs[0]);
+            return (Self::from_limbs(q), Self::from(r));
+        }
+        let (q, r) = div_rem_knuth(&self.limbs, &rhs.limbs);
+        (Self { limbs: q }, Self { limbs: r })
+    }
+
+    /// 下位128bit
+    pub(crate) fn low_u128(&self) -> u128 {
+        self.limbs
+            .iter()
+            .take(4)
+            .rev()
+            .fold(0, |acc, &x| acc << 32 | x as u128)
+    }
+
+    fn mul_add_small(&mut self, mul: u32, add: u32) {
+        let mut carry = add as u64;
+        for x in &mut self.limbs {
+            let t = *x as u64 * mul as u64 + carry;
+            *x = t as u32;
+            carry = t >> 32;
+        }
+        if carry != 0 {
+            self.limbs.push(carry as u32);
+        }
+    }
+
+    /// `radix`進数の文字列から変換する。`radix`は`2..=36`である必要がある。
+    pub fn from_str_radix(s: &str, radix: u32) -> Result<Self, ParseBigIntError> {
+        assert!(
+            (2..=36).contains(&radix),
+            "radix must be in the range 2..=36."
+        );
+        let s = s.strip_prefix('+').unwrap_or(s);
+        if s.is_empty() {
+            return Err(ParseBigIntError::Empty);
+        }
+        // `radix^chunk`が`u32`に収まる桁数ずつまとめて処理する
+        let chunk = (u32::MAX.ilog(radix)) as usize;
+        let mut result = Self::default();
+        for part in s.as_bytes().chunks(chunk) {
+            let mut value = 0;
+            for &c in part {
+                let d = (c as char)
+                    .to_digit(radix)
+                    .ok_or(ParseBigIntError::InvalidDigit)?;
+                value = value * radix + d;
+            }
+            result.mul_add_small(radix.pow(part.len() as u32), value);
+        }
+        Ok(result)
+    }
+
+    /// `radix`進数の文字列に変換する。`radix`は`2..=36`である必要があり、英字は小文字になる。
+    pub fn to_str_radix(&self, radix: u32) -> String {
+        assert!(
+            (2..=36).contains(&radix),
+            "radix must be in the range 2..=36."
+        );
+        if self.is_zero() {
+            return "0".to_string();
+        }
+        let chunk = u32::MAX.ilog(radix);
+        let divisor = radix.pow(chunk);
+        let mut digits = Vec::new();
+        let mut limbs = self.limbs.clone();
+        while !limbs.is_empty() {
+            let (q, mut r) = div_rem_small(&limbs, divisor);
+            limbs = q;
+            for _ in 0..chunk {
+                if limbs.is_empty() && r == 0 {
+                    break;
+                }
+                digits.push(std::char::from_digit(r % radix, radix).unwrap());
+                r /= radix;
+            }
+        }
+        digits.iter().rev().collect()
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        cmp_limbs(&self.limbs, &other.limbs)
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+macro_rules! impl_from_unsigned {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for BigUint {
+                fn from(value: $t) -> Self {
+                    let mut value = value as u128;
+                    let mut limbs = Vec::new();
+                    while value != 0 {
+                        limbs.push(value as u32);
+                        value >>= 32;
+                    }
+                    Self { limbs }
+                }
+            }
+        )*
+    };
+}
+
+impl_from_unsigned!(u8, u16, u32, u64, u128, usize);
+
+impl<'a> Add<&'a BigUint> for &BigUint {
+    type Output = BigUint;
+    fn add(self, rhs: &'a BigUint) -> BigUint {
+        BigUint::from_limbs(add_limbs(&self.limbs, &rhs.limbs))
+    }
+}
+
+impl<'a> Sub<&'a BigUint> for &BigUint {
+    type Output = BigUint;
+    /// `self < rhs`の場合はパニックする。
+    fn sub(self, rhs: &'a BigUint) -> BigUint {
+        assert!(*self >= *rhs, "attempt to subtract with overflow");
+        let mut limbs = self.limbs.clone();
+        sub_assign_limbs(&mut limbs, &rhs.limbs);
+        BigUint::from_limbs(limbs)
+    }
+}
+
+impl<'a> Mul<&'a BigUint> for &BigUint {
+    type Output = BigUint;
+    fn mul(self, rhs: &'a BigUint) -> BigUint {
+        if self.is_zero() || rhs.is_zero() {
+            return BigUint::default();
+        }
+        BigUint::from_limbs(mul_limbs(&self.limbs, &rhs.limbs))
+    }
+}
+
+impl<'a> Div<&'a BigUint> for &BigUint {
+    type Output = BigUint;
+    fn div(self, rhs: &'a BigUint) -> BigUint {
+        self.div_rem(rhs).0
+    }
+}
+
+impl<'a> Rem<&'a BigUint> for &BigUint {
+    type Output = BigUint;
+    fn rem(self, rhs: &'a BigUint) -> BigUint {
+        self.div_rem(rhs).1
+    }
+}
+
+/// 参照同士の演算から、所有権を取る演算と代入演算を実装する。
+macro_rules! forward_binop {
+    ($t:ty, $($trait:ident, $f:ident, $trait_assign:ident, $f_assign:ident);*) => {
+        $(
+            impl $trait<$t> for $t {
+                type Output = $t;
+                fn $f(self, rhs: $t) -> $t {
+                    (&self).$f(&rhs)
+                }
+            }
+
+            impl $trait<&$t> for $t {
+                type Output = $t;
+                fn $f(self, rhs: &$t) -> $t {
+                    (&self).$f(rhs)
+                }
+            }
+
+            impl $trait<$t> for &$t {
+                type Output = $t;
+                fn $f(self, rhs: $t) -> $t {
+                    self.$f(&rhs)
+                }
+            }
+
+            impl $trait_assign<$t> for $t {
+                fn $f_assign(&mut self, rhs: $t) {
+                    *self = (&*self).$f(&rhs);
+                }
+            }
+
+            impl $trait_assign<&$t> for $t {
+                fn $f_assign(&mut self, rhs: &$t) {
+                    *self = (&*self).$f(rhs);
+                }
+            }
+        )*
+    };
+}
+
+pub(crate) use forward_binop;
+
+forward_binop!(
+    BigUint,
+    Add, add, AddAssign, add_assign;
+    Sub, sub, SubAssign, sub_assign;
+    Mul, mul, MulAssign, mul_assign;
+    Div, div, DivAssign, div_assign;
+    Rem, rem, RemAssign, rem_assign
+);
+
+impl Shl<u32> for &BigUint {
+    type Output = BigUint;
+    fn shl(self, rhs: u32) -> BigUint {
+        BigUint {
+            limbs: shl_limbs(&self.limbs, rhs),
+        }
+    }
+}
+
+impl Shr<u32> for &BigUint {
+    type Output = BigUint;
+    fn shr(self, rhs: u32) -> BigUint {
+        BigUint {
+            limbs: shr_limbs(&self.limbs, rhs),
+        }
+    }
+}
+
+impl Shl<u32> for BigUint {
+    type Output = BigUint;
+    fn shl(self, rhs: u32) -> BigUint {
+        &self << rhs
+    }
+}
+
+impl Shr<u32> for BigUint {
+    type Output = BigUint;
+    fn shr(self, rhs: u32) -> BigUint {
+        &self >> rhs
+    }
+}
+
+impl ShlAssign<u32> for BigUint {
+    fn shl_assign(&mut self, rhs: u32) {
+        *self = &*self << rhs;
+    }
+}
+
+impl ShrAssign<u32> for BigUint {
+    fn shr_assign(&mut self, rhs: u32) {
+        *self = &*self >> rhs;
+    }
+}
+
+impl Sum for BigUint {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), |acc, x| acc + x)
+    }
+}
+
+impl Product for BigUint {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::one(), |acc, x| acc * x)
+    }
+}
+
+impl Arithmetic for BigUint {
+    type BitWidthType = u32;
+    fn pow(self, mut exp: u32) -> Self {
+        let mut result = Self::one();
+        let mut base = self;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result *= &base;
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base = &base * &base;
+            }
+        }
+        result
+    }
+
+    fn div_euclid(self, rhs: Self) -> Self {
+        self / rhs
+    }
+
+    fn rem_euclid(self, rhs: Self) -> Self {
+        self % rhs
+    }
+}
+
+impl Integer for BigUint {
+    fn zero() -> Self {
+        Self::default()
+    }
+
+    fn one() -> Self {
+        Self { limbs: vec![1] }
+    }
+}
+
+impl Unsigned for BigUint {}
+
+/// `u128`の2倍幅として使う。`cast_from_twice`は下位128bitを返す。
+impl ExistsBiggerInt for u128 {
+    type Twice = BigUint;
+    fn cast_to_twice(self) -> BigUint {
+        BigUint::from(self)
+    }
+
+    fn cast_from_twice(twice: BigUint) -> Self {
+        twice.low_u128()
+    }
+}
+
+impl FromStr for BigUint {
+    type Err = ParseBigIntError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_str_radix(s, 10)
+    }
+}
+
+impl Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad_integral(true, "", &self.to_str_radix(10))
+    }
+}
+
+impl fmt::LowerHex for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad_integral(true, "0x", &self.to_str_radix(16))
+    }
+}
+
+impl fmt::UpperHex for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad_integral(true, "0x", &self.to_str_radix(16).to_uppercase())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{gcd, lcm};
+    use rand::Rng;
+
+    fn random(rng: &mut impl Rng, len: usize) -> BigUint {
+        BigUint::from_limbs((0..len).map(|_| rng.gen()).collect())
+    }
+
+    #[test]
+    fn small_arithmetic_test() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..1000 {
+            let a = rng.gen::<u64>() as u128;
+            let b = rng.gen::<u64>() as u128 >> rng.gen_range(0..64);
+            let (x, y) = (BigUint::from(a), BigUint::from(b));
+            assert_eq!(&x + &y, BigUint::from(a + b));
+            assert_eq!(&x * &y, BigUint::from(a * b));
+            if a >= b {
+                assert_eq!(&x - &y, BigUint::from(a - b));
+            }
+            if let Some(q) = a.checked_div(b) {
+                assert_eq!(&x / &y, BigUint::from(q));
+                assert_eq!(&x % &y, BigUint::from(a % b));
+            }
+            assert_eq!(x.cmp(&y), a.cmp(&b));
+            assert_eq!(x << 7, BigUint::from(a << 7));
+        }
+        assert_eq!(BigUint::from(3u32).pow(80).low_u128(), 3u128.pow(80));
+        assert_eq!(BigUint::zero().bits(), 0);
+        assert_eq!(BigUint::from(1u128 << 100).bits(), 101);
+        assert_eq!((BigUint::from(1u128 << 100) >> 99).low_u128(), 2);
+        assert!((BigUint::from(5u32) >> 3).is_zero());
+        assert_eq!(u128::cast_from_twice(u128::MAX.cast_to_twice()), u128::MAX);
+    }
+
+    #[test]
+    #[should_panic]
+    fn sub_overflow() {
+        let _ = BigUint::from(1u32) - BigUint::from(2u32);
+    }
+
+    #[test]
+    fn karatsuba_test() {
+        let mut rng = rand::thread_rng();
+        for (n, m) in [(32, 32), (100, 100), (300, 57), (200, 150), (513, 1000)] {
+            let a = random(&mut rng, n);
+            let b = random(&mut rng, m);
+            let expected = BigUint::from_limbs(mul_schoolbook(&a.limbs, &b.limbs));
+            assert_eq!(&a * &b, expected);
+        }
+        // (B^n - 1)^2
+        let a = BigUint::from_limbs(vec![u32::MAX; 100]);
+        let b = (BigUint::one() << 3200) - BigUint::one();
+        assert_eq!(a, b);
+        let expected = (BigUint::one() << 6400) - (BigUint::one() << 3201) + BigUint::one();
+        assert_eq!(&a * &a, expected);
+    }
+
+    #[test]
+    fn division_test() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..300 {
+            let (n, m) = (rng.gen_range(0..40), rng.gen_range(1..20));
+            let a = random(&mut rng, n);
+            let b = random(&mut rng, m);
+            if b.is_zero() {
+                continue;
+            }
+            let (q, r) = a.div_rem(&b);
+            assert!(r < b);
+            assert_eq!(q * &b + r, a);
+        }
+        // 商の推定値の補正や足し戻しが起きやすいように、端の値のリムだけからなる数で試す
+        let edge = [0, 1, 0x7fff_ffff, 0x8000_0000, u32::MAX - 1, u32::MAX];
+        for _ in 0..3000 {
+            let (n, m) = (rng.gen_range(2..8), rng.gen_range(2..5));
+            let a = BigUint::from_limbs((0..n).map(|_| edge[rng.gen_range(0..6)]).collect());
+            let b = BigUint::from_limbs((0..m).map(|_| edge[rng.gen_range(0..6)]).collect());
+            if b.is_zero() {
+                continue;
+            }
+            let (q, r) = a.div_rem(&b);
+            assert!(r < b);
+            assert_eq!(q * &b + r, a);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn divide_by_zero() {
+        let _ = BigUint::one() / BigUint::zero();
+    }
+
+    #[test]
+    fn parse_and_format_test() {
+        let s = "1267650600228229401496703205376";
+        let x: BigUint = s.parse().unwrap();
+        assert_eq!(x, BigUint::one() << 100);
+        assert_eq!(x.to_string(), s);
+        assert_eq!(format!("{x:x}"), "10000000000000000000000000");
+        assert_eq!(
+            format!("{:#X}", BigUint::from(0xdead_beef_u32)),
+            "0xDEADBEEF"
+        );
+        assert_eq!(format!("{:>5}", BigUint::from(42u32)), "   42");
+        assert_eq!(BigUint::zero().to_string(), "0");
+        assert_eq!(
+            BigUint::from_str_radix("fffffffffffffffffffffffffffffffF", 16),
+            Ok(BigUint::from(u128::MAX))
+        );
+        let factorial = (1..=30u32).map(BigUint::from).product::<BigUint>();
+        assert_eq!(factorial.to_string(), "265252859812191058636308480000000");
+        assert_eq!("+007".parse::<BigUint>(), Ok(BigUint::from(7u32)));
+        assert_eq!("".parse::<BigUint>(), Err(ParseBigIntError::Empty));
+        assert_eq!(
+            "12a".parse::<BigUint>(),
+            Err(ParseBigIntError::InvalidDigit)
+        );
+        assert_eq!("-1".parse::<BigUint>(), Err(ParseBigIntError::InvalidDigit));
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..50 {
+            let len = rng.gen_range(0..30);
+            let x = random(&mut rng, len);
+            for radix in [2, 10, 16, 36] {
+                assert_eq!(
+                    BigUint::from_str_radix(&x.to_str_radix(radix), radix),
+                    Ok(x.clone())
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn gcd_test() {
+        let a = BigUint::from(3u32) << 100;
+        let b = BigUint::from(9u32) << 50;
+        assert_eq!(gcd(a.clone(), b.clone()), BigUint::from(3u32) << 50);
+        assert_eq!(lcm(a, b), BigUint::from(9u32) << 100);
+        let f = |n: u32| (1..=n).map(BigUint::from).product::<BigUint>();
+        assert_eq!(gcd(f(50), f(40) * BigUint::from(53u32)), f(40));
+    }
+}
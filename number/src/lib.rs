@@ -1,4 +1,16 @@
+pub mod bigint;
+pub mod biguint;
+pub mod modular;
+pub mod quotient;
+pub mod rational;
+pub mod sieve;
 pub mod traits;
+pub use bigint::BigInt;
+pub use biguint::{BigUint, ParseBigIntError};
+pub use modular::{crt, garner, gcd_ext, mod_inverse, solve_linear_congruence};
+pub use quotient::{floor_sum, quotient_blocks, QuotientBlocks};
+pub use rational::Rational;
+pub use sieve::{count_primes, segmented_sieve, LinearSieve};
 
 pub fn gcd<T: traits::Integer + Eq + Clone>(mut a: T, mut b: T) -> T {
     let zero = T::zero();
@@ -13,6 +25,16 @@ pub fn gcd<T: traits::Integer + Eq + Clone>(mut a: T, mut b: T) -> T {
     a
 }
 
+/// 最小公倍数。`a`と`b`は非負である必要があり、どちらかが0の場合は0を返す。
+pub fn lcm<T: traits::Integer + Eq + Clone>(a: T, b: T) -> T {
+    let zero = T::zero();
+    if a == zero || b == zero {
+        return zero;
+    }
+    let g = gcd(a.clone(), b.clone());
+    a / g * b
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -23,4 +45,12 @@ mod tests {
         let b = 24u32;
         assert_eq!(gcd(a, b), 6);
     }
+
+    #[test]
+    fn lcm_test() {
+        assert_eq!(lcm(18u32, 24), 72);
+        assert_eq!(lcm(0u32, 24), 0);
+        assert_eq!(lcm(7i64, 1), 7);
+        assert_eq!(lcm(u64::MAX, u64::MAX), u64::MAX);
+    }
 }
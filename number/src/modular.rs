@@ -0,0 +1,198 @@
+use crate::traits::{Integer, Signed};
+
+/// `a * x + b * y = g`となる`(g, x, y)`を拡張ユークリッドの互除法で求める。`g = gcd(a, b)`は非負。
+pub fn gcd_ext<T: Integer + Signed + Clone>(a: T, b: T) -> (T, T, T) {
+    let (mut r0, mut r1) = (a, b);
+    let (mut x0, mut x1) = (T::one(), T::zero());
+    let (mut y0, mut y1) = (T::zero(), T::one());
+    while r1 != T::zero() {
+        let q = r0.clone() / r1.clone();
+        let r = r0 - q.clone() * r1.clone();
+        let x = x0 - q.clone() * x1.clone();
+        let y = y0 - q * y1.clone();
+        (r0, r1) = (r1, r);
+        (x0, x1) = (x1, x);
+        (y0, y1) = (y1, y);
+    }
+    if r0 < T::zero() {
+        (T::zero() - r0, T::zero() - x0, T::zero() - y0)
+    } else {
+        (r0, x0, y0)
+    }
+}
+
+/// `x * a ≡ 1 (mod m)`となる`0 <= x < m`を求める。
+/// `a`と`m`が互いに素でない場合は`None`を返す。`m`は正である必要がある。
+pub fn mod_inverse<T: Integer + Signed + Clone>(a: T, m: T) -> Option<T> {
+    let (g, x, _) = gcd_ext(a.rem_euclid(m.clone()), m.clone());
+    (g == T::one()).then(|| x.rem_euclid(m))
+}
+
+/// `a * x ≡ b (mod m)`を解く。
+///
+/// 解が存在する場合、解全体は`x ≡ x0 (mod m / gcd(a, m))`と表せるので`(x0, m / gcd(a, m))`を返す。
+/// `m`は正である必要がある。
+pub fn solve_linear_congruence<T: Integer + Signed + Clone>(a: T, b: T, m: T) -> Option<(T, T)> {
+    let a = a.rem_euclid(m.clone());
+    let b = b.rem_euclid(m.clone());
+    let (g, x, _) = gcd_ext(a, m.clone());
+    if b.clone() % g.clone() != T::zero() {
+        return None;
+    }
+    let m = m / g.clone();
+    // `(a / g) * x ≡ 1 (mod m / g)`
+    let x = x.rem_euclid(m.clone());
+    Some((b / g * x % m.clone(), m))
+}
+
+/// 連立合同式`x ≡ r_i (mod m_i)`を解く。法は互いに素でなくてもよい。
+///
+/// 解が存在する場合、`x ≡ r (mod lcm(m_i))`となる`(r, lcm(m_i))`を返す。
+/// 空の場合は`(0, 1)`を返す。
+/// `m_i`は正で、計算途中で`lcm(m_i)`と`max(m_i)^2`が`T`の範囲に収まる必要がある。
+pub fn crt<T: Integer + Signed + Clone>(congruences: &[(T, T)]) -> Option<(T, T)> {
+    let (mut r, mut m) = (T::zero(), T::one());
+    for (ri, mi) in congruences {
+        let (mut ri, mut mi) = (ri.clone().rem_euclid(mi.clone()), mi.clone());
+        if m < mi {
+            std::mem::swap(&mut r, &mut ri);
+            std::mem::swap(&mut m, &mut mi);
+        }
+        if m.clone() % mi.clone() == T::zero() {
+            if r.clone() % mi != ri {
+                return None;
+            }
+            continue;
+        }
+        // r + m * t ≡ ri (mod mi)
+        let (g, inv, _) = gcd_ext(m.clone(), mi.clone());
+        let diff = ri - r.clone();
+        if diff.clone() % g.clone() != T::zero() {
+            return None;
+        }
+        let u = mi / g.clone();
+        let t = (diff / g).rem_euclid(u.clone()) * inv.rem_euclid(u.clone()) % u.clone();
+        r += t * m.clone();
+        m *= u;
+    }
+    Some((r, m))
+}
+
+/// Garnerのアルゴリズムで、連立合同式`x ≡ r_i (mod m_i)`の最小の非負整数解を`modulus`で割った余りを求める。
+///
+/// `m_i`は正で、どの2つも互いに素である必要がある。
+/// `max(m_i, modulus)^2`が`T`の範囲に収まる必要がある。
+pub fn garner<T: Integer + Signed + Clone>(congruences: &[(T, T)], modulus: T) -> T {
+    let n = congruences.len();
+    let moduli = congruences
+        .iter()
+        .map(|(_, m)| m.clone())
+        .chain(std::iter::once(modulus))
+        .collect::<Vec<_>>();
+    // x = constants[j] + coeffs[j] * (t_i + m_i * (...)) (mod moduli[j])
+    let mut coeffs = vec![T::one(); n + 1];
+    let mut constants = vec![T::zero(); n + 1];
+    for (i, (r, m)) in congruences.iter().enumerate() {
+        let inv =
+            mod_inverse(coeffs[i].clone(), m.clone()).expect("moduli must be pairwise coprime");
+        let t = (r.clone() - constants[i].clone()).rem_euclid(m.clone()) * inv % m.clone();
+        for j in i + 1..=n {
+            constants[j] =
+                (constants[j].clone() + t.clone() * coeffs[j].clone()) % moduli[j].clone();
+            coeffs[j] = coeffs[j].clone() * (m.clone() % moduli[j].clone()) % moduli[j].clone();
+        }
+    }
+    constants[n].clone().rem_euclid(moduli[n].clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gcd;
+
+    #[test]
+    fn gcd_ext_test() {
+        for a in -30i64..=30 {
+            for b in -30i64..=30 {
+                let (g, x, y) = gcd_ext(a, b);
+                assert_eq!(a * x + b * y, g);
+                assert_eq!(g, gcd(a.abs(), b.abs()));
+            }
+        }
+        assert_eq!(gcd_ext(i32::MAX, i32::MAX - 1).0, 1);
+    }
+
+    #[test]
+    fn mod_inverse_test() {
+        assert_eq!(mod_inverse(3i64, 7), Some(5));
+        assert_eq!(mod_inverse(-3i64, 7), Some(2));
+        assert_eq!(mod_inverse(4i64, 8), None);
+        assert_eq!(mod_inverse(5i64, 1), Some(0));
+        for m in 1..50i32 {
+            for a in 0..m {
+                match mod_inverse(a, m) {
+                    Some(x) => assert_eq!(a * x % m, 1 % m),
+                    None => assert_ne!(gcd(a, m), 1),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn linear_congruence_test() {
+        assert_eq!(solve_linear_congruence(4i64, 6, 10), Some((4, 5)));
+        assert_eq!(solve_linear_congruence(4i64, 5, 10), None);
+        for m in 1..30i32 {
+            for a in -m..m {
+                for b in 0..m {
+                    let expected = (0..m)
+                        .filter(|x| (a * x - b).rem_euclid(m) == 0)
+                        .collect::<Vec<_>>();
+                    match solve_linear_congruence(a, b, m) {
+                        Some((x0, step)) => {
+                            let solutions =
+                                (0..m).filter(|x| (x - x0) % step == 0).collect::<Vec<_>>();
+                            assert_eq!(solutions, expected);
+                            assert!(0 <= x0 && x0 < step);
+                        }
+                        None => assert!(expected.is_empty()),
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn crt_test() {
+        assert_eq!(crt::<i64>(&[]), Some((0, 1)));
+        assert_eq!(crt(&[(2i64, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(1i64, 4), (3, 6)]), Some((9, 12)));
+        assert_eq!(crt(&[(1i64, 4), (2, 6)]), None);
+        assert_eq!(crt(&[(-1i64, 6), (3, 3)]), None);
+        assert_eq!(crt(&[(5i64, 6), (2, 3)]), Some((5, 6)));
+        for m1 in 1..20i64 {
+            for m2 in 1..20 {
+                for r1 in 0..m1 {
+                    for r2 in 0..m2 {
+                        let l = m1 / gcd(m1, m2) * m2;
+                        let expected = (0..l).find(|x| x % m1 == r1 && x % m2 == r2);
+                        assert_eq!(crt(&[(r1, m1), (r2, m2)]), expected.map(|x| (x, l)));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn garner_test() {
+        let congruences = [(2i64, 3), (3, 5), (2, 7)];
+        assert_eq!(garner(&congruences, 1_000_000_007), 23);
+        assert_eq!(garner(&congruences, 10), 3);
+        let x = 123_456_789_012_345_678i128;
+        let moduli = [998244353i128, 1_000_000_007, 1_000_000_009];
+        let congruences = moduli.map(|m| (x % m, m));
+        assert_eq!(garner(&congruences, i64::MAX as i128), x);
+        assert_eq!(garner(&congruences, 1_000_003), x % 1_000_003);
+        assert_eq!(garner::<i64>(&[], 7), 0);
+    }
+}
@@ -0,0 +1,166 @@
+use std::iter::FusedIterator;
+
+use crate::traits::Integer;
+
+/// `n(n - 1) / 2`を途中の値が大きくならないように計算する。
+fn triangular<T: Integer>(n: T) -> T {
+    let two = T::one() + T::one();
+    if n == T::zero() {
+        T::zero()
+    } else if n.clone() % two.clone() == T::zero() {
+        n.clone() / two * (n - T::one())
+    } else {
+        (n.clone() - T::one()) / two * n
+    }
+}
+
+/// `Σ_{i=0}^{n-1} floor((a * i + b) / m)`をO(log m)で求める。
+///
+/// `n >= 0`、`m > 0`である必要がある。`a`、`b`は負でもよい。
+/// 途中で`a * n + b`程度の値が現れるので、`T`の範囲に収まる必要がある。
+pub fn floor_sum<T: Integer>(n: T, m: T, a: T, b: T) -> T {
+    assert!(n >= T::zero(), "n must be non-negative.");
+    assert!(m > T::zero(), "m must be positive.");
+    let (mut n, mut m, mut a, mut b) = (n, m, a, b);
+    let mut ans = T::zero();
+    // a, bを[0, m)に直す
+    if a < T::zero() {
+        let a2 = a.clone().rem_euclid(m.clone());
+        ans -= triangular(n.clone()) * ((a2.clone() - a) / m.clone());
+        a = a2;
+    }
+    if b < T::zero() {
+        let b2 = b.clone().rem_euclid(m.clone());
+        ans -= n.clone() * ((b2.clone() - b) / m.clone());
+        b = b2;
+    }
+    loop {
+        if a >= m {
+            ans += triangular(n.clone()) * (a.clone() / m.clone());
+            a %= m.clone();
+        }
+        if b >= m {
+            ans += n.clone() * (b.clone() / m.clone());
+            b %= m.clone();
+        }
+        // 直線 y = (a * x + b) / m の下の格子点を、x と y を入れ替えて数える
+        let y_max = a.clone() * n + b;
+        if y_max < m {
+            break;
+        }
+        n = y_max.clone() / m.clone();
+        b = y_max % m.clone();
+        std::mem::swap(&mut m, &mut a);
+    }
+    ans
+}
+
+/// `1 <= i <= n`を`n / i`の値が等しい区間に分ける。
+///
+/// `(l, r, q)`を`l`の昇順に返す。`l <= i <= r`で`n / i = q`となる。区間の数はO(√n)個。
+pub fn quotient_blocks<T: Integer>(n: T) -> QuotientBlocks<T> {
+    QuotientBlocks {
+        next: (n >= T::one()).then(T::one),
+        n,
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct QuotientBlocks<T> {
+    n: T,
+    next: Option<T>,
+}
+
+impl<T: Integer> Iterator for QuotientBlocks<T> {
+    type Item = (T, T, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let l = self.next.take()?;
+        let q = self.n.clone() / l.clone();
+        let r = self.n.clone() / q.clone();
+        // `r = n`のとき`r + 1`はオーバーフローし得る
+        if r < self.n {
+            self.next = Some(r.clone() + T::one());
+        }
+        Some((l, r, q))
+    }
+}
+
+impl<T: Integer> FusedIterator for QuotientBlocks<T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{traits::Arithmetic, BigInt};
+    use rand::Rng;
+
+    #[test]
+    fn floor_sum_test() {
+        for n in 0..20i64 {
+            for m in 1..20 {
+                for a in -20..20 {
+                    for b in -20..20 {
+                        let expected = (0..n).map(|i| (a * i + b).div_euclid(m)).sum::<i64>();
+                        assert_eq!(floor_sum(n, m, a, b), expected, "{n} {m} {a} {b}");
+                    }
+                }
+            }
+        }
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let n = rng.gen_range(0..1000u64);
+            let m = rng.gen_range(1..1_000_000_000u64);
+            let a = rng.gen_range(0..1_000_000_000u64);
+            let b = rng.gen_range(0..1_000_000_000u64);
+            let expected = (0..n).map(|i| (a * i + b) / m).sum::<u64>();
+            assert_eq!(floor_sum(n, m, a, b), expected);
+        }
+        assert_eq!(floor_sum(0u8, 1, 255, 255), 0);
+        let big = floor_sum(
+            BigInt::from(10).pow(9),
+            BigInt::from(998244353),
+            BigInt::from(-123456789),
+            BigInt::from(10).pow(18),
+        );
+        let expected = floor_sum(10i128.pow(9), 998244353, -123456789, 10i128.pow(18));
+        assert_eq!(big, BigInt::from(expected));
+    }
+
+    #[test]
+    fn quotient_blocks_test() {
+        for n in 0..300u32 {
+            let blocks = quotient_blocks(n).collect::<Vec<_>>();
+            let mut expected = Vec::<(u32, u32, u32)>::new();
+            for i in 1..=n {
+                match expected.last_mut() {
+                    Some((_, r, q)) if *q == n / i => *r = i,
+                    _ => expected.push((i, i, n / i)),
+                }
+            }
+            assert_eq!(blocks, expected);
+            assert!(blocks.len() as u32 <= 2 * n.isqrt() + 1);
+        }
+        assert_eq!(quotient_blocks(-5i32).count(), 0);
+        for n in [u8::MAX, 128, 127] {
+            let blocks = quotient_blocks(n).collect::<Vec<_>>();
+            assert_eq!(blocks.last(), Some(&(n / 2 + 1, n, 1)));
+            assert_eq!(
+                blocks
+                    .iter()
+                    .map(|&(l, r, _)| (r - l) as u32 + 1)
+                    .sum::<u32>(),
+                n as u32
+            );
+        }
+        assert_eq!(
+            quotient_blocks(i32::MAX).last(),
+            Some((i32::MAX / 2 + 1, i32::MAX, 1))
+        );
+        let n = 10u64.pow(12);
+        let sum = quotient_blocks(n)
+            .map(|(l, r, q)| (r - l + 1) * q)
+            .sum::<u64>();
+        // Σ n / i = Σ d(k) (k <= n)
+        assert_eq!(sum, 27785452449086);
+    }
+}
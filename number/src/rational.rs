@@ -0,0 +1,435 @@
+use std::{
+    cmp::Ordering,
+    fmt::{self, Display},
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign},
+};
+
+use crate::{
+    gcd,
+    traits::{Arithmetic, ExistsBiggerInt, Integer, Signed},
+};
+
+fn abs<T: Integer>(x: T) -> T {
+    if x < T::zero() {
+        T::zero() - x
+    } else {
+        x
+    }
+}
+
+/// 有理数。常に既約で、分母は正に保たれる。
+///
+/// 演算の途中では分子と分母の積が現れるので、`T`の範囲に収まる必要がある。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational<T> {
+    num: T,
+    den: T,
+}
+
+impl<T: Integer + Clone> Rational<T> {
+    /// `num / den`を作る。`den`が0の場合はパニックする。
+    pub fn new(num: T, den: T) -> Self {
+        assert!(den != T::zero(), "denominator must be nonzero.");
+        let g = abs(gcd(num.clone(), den.clone()));
+        let (mut num, mut den) = (num / g.clone(), den / g);
+        if den < T::zero() {
+            num = T::zero() - num;
+            den = T::zero() - den;
+        }
+        Self { num, den }
+    }
+
+    /// 約分済みであることが分かっている場合に使う。
+    fn new_unchecked(num: T, den: T) -> Self {
+        Self { num, den }
+    }
+
+    pub fn numer(&self) -> &T {
+        &self.num
+    }
+
+    pub fn denom(&self) -> &T {
+        &self.den
+    }
+
+    pub fn into_parts(self) -> (T, T) {
+        (self.num, self.den)
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.den == T::one()
+    }
+
+    /// 逆数。`0`の場合はパニックする。
+    pub fn recip(self) -> Self {
+        Self::new(self.den, self.num)
+    }
+
+    /// 以下の最大の整数
+    pub fn floor(&self) -> T {
+        self.num.clone().div_euclid(self.den.clone())
+    }
+
+    /// 以上の最小の整数
+    pub fn ceil(&self) -> T {
+        let q = self.floor();
+        if q.clone() * self.den.clone() == self.num {
+            q
+        } else {
+            q + T::one()
+        }
+    }
+
+    /// 最も近い整数。ちょうど中間の場合は0から遠い方に丸める。
+    pub fn round(&self) -> T {
+        let q = self.floor();
+        // 0 <= r < den
+        let r = self.num.clone() - q.clone() * self.den.clone();
+        let rest = self.den.clone() - r.clone();
+        match r.cmp(&rest) {
+            Ordering::Less => q,
+            Ordering::Greater => q + T::one(),
+            Ordering::Equal if self.num < T::zero() => q,
+            Ordering::Equal => q + T::one(),
+        }
+    }
+
+    /// 整数部分(0方向に切り捨てた値)
+    pub fn trunc(&self) -> T {
+        self.num.clone() / self.den.clone()
+    }
+}
+
+impl<T: ExistsBiggerInt + Clone> Rational<T> {
+    /// 分母が`max_den`以下の有理数のうち、`self`に最も近いものを求める。
+    /// 最も近いものが2つある場合は分母が小さい方を、分母も等しい場合は絶対値が小さい方を返す。
+    /// `max_den`は正である必要がある。
+    ///
+    /// Stern–Brocot木を`self`に向かって下り、同じ方向に進む区間をまとめて処理するのでO(log max_den)で動く。
+    pub fn approximate(&self, max_den: T) -> Self {
+        assert!(max_den >= T::one(), "max_den must be positive.");
+        if self.den <= max_den {
+            return self.clone();
+        }
+        // 負の場合は`T`が符号付きなので、絶対値で求めてから符号を戻す
+        if self.num < T::zero() {
+            let neg = Self::new_unchecked(T::zero() - self.num.clone(), self.den.clone());
+            let r = neg.approximate(max_den);
+            return Self::new_unchecked(T::zero() - r.num, r.den);
+        }
+        // 現在の区間は`[p0 / q0, p1 / q1]`の形の隣接する分数で挟まれている
+        let (mut p0, mut q0, mut p1, mut q1) = (T::zero(), T::one(), T::one(), T::zero());
+        let (mut n, mut d) = (self.num.clone(), self.den.clone());
+        loop {
+            let a = n.clone() / d.clone();
+            let q2 = q0.clone() + a.clone() * q1.clone();
+            if q2 > max_den {
+                break;
+            }
+            let p2 = p0 + a.clone() * p1.clone();
+            (p0, q0, p1, q1) = (p1, q1, p2, q2);
+            let r = n - a * d.clone();
+            (n, d) = (d, r);
+        }
+        // 同じ方向にあと`k`回だけ進める
+        let k = (max_den - q0.clone()) / q1.clone();
+        let bound1 = Self::new_unchecked(p0 + k.clone() * p1.clone(), q0 + k * q1.clone());
+        let bound2 = Self::new_unchecked(p1, q1);
+        let dist = |r: &Self| {
+            if r > self {
+                r.clone() - self.clone()
+            } else {
+                self.clone() - r.clone()
+            }
+        };
+        match dist(&bound1).cmp(&dist(&bound2)) {
+            Ordering::Less => bound1,
+            Ordering::Greater => bound2,
+            // 分母が等しくなるのは`bound1 = bound2 + 1`の場合だけ
+            Ordering::Equal if bound1.den < bound2.den => bound1,
+            Ordering::Equal => bound2,
+        }
+    }
+}
+
+impl<T: Integer + Clone> From<T> for Rational<T> {
+    fn from(value: T) -> Self {
+        Self::new_unchecked(value, T::one())
+    }
+}
+
+impl<T: ExistsBiggerInt + Clone> Ord for Rational<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // 分母は正なので`a / b < c / d`と`a * d < c * b`は同値
+        let lhs = self.num.clone().cast_to_twice() * other.den.clone().cast_to_twice();
+        let rhs = other.num.clone().cast_to_twice() * self.den.clone().cast_to_twice();
+        lhs.cmp(&rhs)
+    }
+}
+
+impl<T: ExistsBiggerInt + Clone> PartialOrd for Rational<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Integer + Clone> Add for Rational<T> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        // 分母の最大公約数で割っておくことで途中の値を小さくする
+        let g = gcd(self.den.clone(), rhs.den.clone());
+        let (a, b) = (self.den.clone() / g.clone(), rhs.den.clone() / g);
+        Self::new(self.num * b.clone() + rhs.num * a, self.den * b)
+    }
+}
+
+impl<T: Integer + Clone> Sub for Rational<T> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        let g = gcd(self.den.clone(), rhs.den.clone());
+        let (a, b) = (self.den.clone() / g.clone(), rhs.den.clone() / g);
+        Self::new(self.num * b.clone() - rhs.num * a, self.den * b)
+    }
+}
+
+impl<T: Integer + Clone> Mul for Rational<T> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        if self.num == T::zero() || rhs.num == T::zero() {
+            return Self::from(T::zero());
+        }
+        // 既約なので、たすき掛けで約分すれば積も既約になる
+        let g1 = abs(gcd(self.num.clone(), rhs.den.clone()));
+        let g2 = abs(gcd(rhs.num.clone(), self.den.clone()));
+        Self::new_unchecked(
+            (self.num / g1.clone()) * (rhs.num / g2.clone()),
+            (self.den / g2) * (rhs.den / g1),
+        )
+    }
+}
+
+impl<T: Integer + Clone> Div for Rational<T> {
+    type Output = Self;
+    /// `rhs`が`0`の場合はパニックする。
+    fn div(self, rhs: Self) -> Self {
+        Mul::mul(self, rhs.recip())
+    }
+}
+
+impl<T: Integer + Clone> Rem for Rational<T> {
+    type Output = Self;
+    /// `self - rhs * trunc(self / rhs)`。浮動小数点数の剰余と同じく、符号は`self`と一致する。
+    fn rem(self, rhs: Self) -> Self {
+        let q = (self.clone() / rhs.clone()).trunc();
+        self - rhs * Self::from(q)
+    }
+}
+
+macro_rules! impl_assign_ops {
+    ($($trait:ident, $f:ident, $op:tt);*) => {
+        $(
+            impl<T: Integer + Clone> $trait for Rational<T> {
+                fn $f(&mut self, rhs: Self) {
+                    *self = self.clone() $op rhs;
+                }
+            }
+        )*
+    };
+}
+
+impl_assign_ops!(
+    AddAssign, add_assign, +;
+    SubAssign, sub_assign, -;
+    MulAssign, mul_assign, *;
+    DivAssign, div_assign, /;
+    RemAssign, rem_assign, %
+);
+
+impl<T: Integer + Signed + Clone> Neg for Rational<T> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self::new_unchecked(T::zero() - self.num, self.den)
+    }
+}
+
+impl<T: Integer + Clone> Arithmetic for Rational<T> {
+    type BitWidthType = u32;
+    fn pow(self, exp: u32) -> Self {
+        // 既約分数の冪は既約
+        let mut result = Self::from(T::one());
+        let mut base = self;
+        let mut exp = exp;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = Self::new_unchecked(
+                    result.num * base.num.clone(),
+                    result.den * base.den.clone(),
+                );
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base =
+                    Self::new_unchecked(base.num.clone() * base.num, base.den.clone() * base.den);
+            }
+        }
+        result
+    }
+
+    /// `self = rhs * q + r`、`0 <= r < |rhs|`となる整数`q`
+    fn div_euclid(self, rhs: Self) -> Self {
+        let q = self.clone() / rhs.clone();
+        if rhs.num > T::zero() {
+            Self::from(q.floor())
+        } else {
+            Self::from(q.ceil())
+        }
+    }
+
+    fn rem_euclid(self, rhs: Self) -> Self {
+        let q = self.clone().div_euclid(rhs.clone());
+        self - rhs * q
+    }
+}
+
+impl<T: Integer + Clone + Display> Display for Rational<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BigInt;
+
+    fn r(num: i64, den: i64) -> Rational<i64> {
+        Rational::new(num, den)
+    }
+
+    #[test]
+    fn normalize_test() {
+        assert_eq!(r(6, -4).into_parts(), (-3, 2));
+        assert_eq!(r(0, -5).into_parts(), (0, 1));
+        assert_eq!(Rational::new(6u32, 4).into_parts(), (3, 2));
+        assert!(r(4, 2).is_integer());
+        assert_eq!(r(-3, 2).to_string(), "-3/2");
+        assert_eq!(r(4, 2).to_string(), "2");
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_denominator() {
+        r(1, 0);
+    }
+
+    #[test]
+    fn arithmetic_test() {
+        for a in -6..=6 {
+            for b in 1..=6 {
+                for c in -6..=6 {
+                    for d in 1..=6 {
+                        let (x, y) = (r(a, b), r(c, d));
+                        assert_eq!(x + y, r(a * d + c * b, b * d));
+                        assert_eq!(x - y, r(a * d - c * b, b * d));
+                        assert_eq!(x * y, r(a * c, b * d));
+                        if c != 0 {
+                            assert_eq!(x / y, r(a * d, b * c));
+                            let q = x.div_euclid(y);
+                            let rem = x.rem_euclid(y);
+                            assert!(q.is_integer());
+                            assert!(rem >= r(0, 1) && rem < r(c.abs(), d));
+                            assert_eq!(y * q + rem, x);
+                            let rem = x % y;
+                            assert!(rem == r(0, 1) || (rem < r(0, 1)) == (x < r(0, 1)));
+                        }
+                        assert_eq!(x < y, (a * d) < (c * b));
+                    }
+                }
+            }
+        }
+        assert_eq!(r(-2, 3).pow(3), r(-8, 27));
+        assert_eq!(r(-2, 3).pow(0), r(1, 1));
+        assert_eq!(-r(2, 3), r(-2, 3));
+        let mut x = r(1, 2);
+        x += r(1, 3);
+        x *= r(6, 1);
+        assert_eq!(x, r(5, 1));
+    }
+
+    #[test]
+    fn compare_without_overflow_test() {
+        let a = r(i64::MAX, i64::MAX - 1);
+        let b = r(i64::MAX - 1, i64::MAX - 2);
+        assert!(a < b);
+        assert!(r(i64::MIN + 1, 3) < r(i64::MIN + 2, 3));
+        assert_eq!(a.cmp(&a), Ordering::Equal);
+    }
+
+    #[test]
+    fn rounding_test() {
+        let cases = [
+            (r(7, 2), 3, 4, 4, 3),
+            (r(-7, 2), -4, -3, -4, -3),
+            (r(5, 3), 1, 2, 2, 1),
+            (r(-5, 3), -2, -1, -2, -1),
+            (r(4, 3), 1, 2, 1, 1),
+            (r(-4, 3), -2, -1, -1, -1),
+            (r(3, 1), 3, 3, 3, 3),
+            (r(0, 1), 0, 0, 0, 0),
+        ];
+        for (x, floor, ceil, round, trunc) in cases {
+            assert_eq!(x.floor(), floor, "{x}");
+            assert_eq!(x.ceil(), ceil, "{x}");
+            assert_eq!(x.round(), round, "{x}");
+            assert_eq!(x.trunc(), trunc, "{x}");
+        }
+        assert_eq!(Rational::new(5u32, 2).round(), 3);
+    }
+
+    #[test]
+    fn approximate_test() {
+        let pi = r(3_141_592_653_589_793, 1_000_000_000_000_000);
+        assert_eq!(pi.approximate(1), r(3, 1));
+        assert_eq!(pi.approximate(7), r(22, 7));
+        assert_eq!(pi.approximate(100), r(311, 99));
+        assert_eq!(pi.approximate(1000), r(355, 113));
+        assert_eq!((-pi).approximate(1000), r(-355, 113));
+        assert_eq!(r(1, 3).approximate(3), r(1, 3));
+        // 全探索と比較する
+        for num in -60..=60 {
+            for den in 1..=30 {
+                let x = r(num, den);
+                for max_den in 1..=10 {
+                    let best = (1..=max_den)
+                        .flat_map(|q| {
+                            let p = (x * r(q, 1)).round();
+                            [r(p - 1, q), r(p, q), r(p + 1, q)]
+                        })
+                        .min_by_key(|&y| {
+                            let d = if y > x { y - x } else { x - y };
+                            (d, *y.denom(), y.numer().abs())
+                        })
+                        .unwrap();
+                    assert_eq!(x.approximate(max_den), best, "{x} {max_den}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn bigint_test() {
+        let x = Rational::new(BigInt::from(1), BigInt::from(3));
+        let y = Rational::new(BigInt::from(-1), BigInt::from(6));
+        assert_eq!(
+            (x.clone() + y.clone()).into_parts(),
+            (BigInt::from(1), BigInt::from(6))
+        );
+        let big = x.pow(100) * Rational::from(BigInt::from(3).pow(99));
+        assert_eq!(big.into_parts(), (BigInt::from(1), BigInt::from(3)));
+        assert_eq!(y.floor(), BigInt::from(-1));
+    }
+}
@@ -0,0 +1,335 @@
+use crate::traits::{Integer, Signed};
+
+fn from_u64<T: TryFrom<u64>>(x: u64) -> T {
+    T::try_from(x).unwrap_or_else(|_| panic!("{x} does not fit in the integer type"))
+}
+
+fn to_u64<T: TryInto<u64>>(x: T) -> u64 {
+    x.try_into()
+        .unwrap_or_else(|_| panic!("value must be non-negative and fit in u64"))
+}
+
+/// 線形篩。`0..=n`の各整数の最小素因数を持つ。
+#[derive(Debug, Clone)]
+pub struct LinearSieve<T> {
+    /// `spf[i]`は`i`の最小素因数。`spf[0] = spf[1] = 0`
+    spf: Vec<T>,
+    primes: Vec<T>,
+}
+
+impl<T> LinearSieve<T>
+where
+    T: Integer + Copy + TryFrom<u64> + TryInto<u64>,
+{
+    /// `0..=n`の篩をO(n)で作る。
+    pub fn new(n: usize) -> Self {
+        let mut spf = vec![0u32; n + 1];
+        let mut primes = Vec::new();
+        for i in 2..=n {
+            if spf[i] == 0 {
+                spf[i] = i as u32;
+                primes.push(i as u32);
+            }
+            for &p in &primes {
+                if p > spf[i] || i * p as usize > n {
+                    break;
+                }
+                spf[i * p as usize] = p;
+            }
+        }
+        Self {
+            spf: spf.into_iter().map(|p| from_u64(p as u64)).collect(),
+            primes: primes.into_iter().map(|p| from_u64(p as u64)).collect(),
+        }
+    }
+
+    /// 篩の上限`n`
+    pub fn max(&self) -> usize {
+        self.spf.len() - 1
+    }
+
+    /// `n`以下の素数を昇順に返す。
+    pub fn primes(&self) -> &[T] {
+        &self.primes
+    }
+
+    pub fn is_prime(&self, x: T) -> bool {
+        let i = to_u64(x) as usize;
+        i >= 2 && self.spf[i] == x
+    }
+
+    /// `x`の最小素因数。`x`は2以上である必要がある。
+    pub fn smallest_prime_factor(&self, x: T) -> T {
+        let i = to_u64(x) as usize;
+        assert!(i >= 2, "x must be at least 2.");
+        self.spf[i]
+    }
+
+    /// `x`を素因数分解し、素因数とその指数の組を素因数の昇順に返す。
+    pub fn factorize(&self, x: T) -> Vec<(T, u32)> {
+        let mut x = to_u64(x) as usize;
+        assert!(x >= 1, "x must be positive.");
+        let mut factors: Vec<(T, u32)> = Vec::new();
+        while x > 1 {
+            let p = self.spf[x];
+            match factors.last_mut() {
+                Some((q, e)) if *q == p => *e += 1,
+                _ => factors.push((p, 1)),
+            }
+            x /= to_u64(p) as usize;
+        }
+        factors
+    }
+
+    /// 乗法的関数`f`を`0..=n`について求める。`f(0)`は`0`、`f(1)`は`1`とする。
+    ///
+    /// `f_pe(p, e)`は素数冪`p^e`での値を返す。
+    fn multiplicative_table(&self, mut f_pe: impl FnMut(u64, u32) -> i64) -> Vec<i64> {
+        let n = self.max();
+        let mut table = vec![0i64; n + 1];
+        // `rest[i]`は`i`から最小素因数を全て取り除いた値、`exp[i]`はその指数
+        let mut rest = vec![0usize; n + 1];
+        let mut exp = vec![0u32; n + 1];
+        if n >= 1 {
+            table[1] = 1;
+        }
+        for i in 2..=n {
+            let p = to_u64(self.spf[i]) as usize;
+            let j = i / p;
+            if j >= 2 && to_u64(self.spf[j]) as usize == p {
+                rest[i] = rest[j];
+                exp[i] = exp[j] + 1;
+            } else {
+                rest[i] = j;
+                exp[i] = 1;
+            }
+            table[i] = table[rest[i]] * f_pe(p as u64, exp[i]);
+        }
+        table
+    }
+
+    /// オイラーのトーシェント関数`φ(i)`の`0..=n`のテーブル
+    pub fn euler_phi_table(&self) -> Vec<T> {
+        self.multiplicative_table(|p, e| ((p - 1) * p.pow(e - 1)) as i64)
+            .into_iter()
+            .map(|x| from_u64(x as u64))
+            .collect()
+    }
+
+    /// 約数の個数`d(i)`の`0..=n`のテーブル
+    pub fn divisor_count_table(&self) -> Vec<T> {
+        self.multiplicative_table(|_, e| e as i64 + 1)
+            .into_iter()
+            .map(|x| from_u64(x as u64))
+            .collect()
+    }
+
+    /// メビウス関数`μ(i)`の`0..=n`のテーブル
+    pub fn mobius_table(&self) -> Vec<T>
+    where
+        T: Signed,
+    {
+        self.multiplicative_table(|_, e| if e == 1 { -1 } else { 0 })
+            .into_iter()
+            .map(|x| match x {
+                -1 => T::zero() - T::one(),
+                0 => T::zero(),
+                _ => T::one(),
+            })
+            .collect()
+    }
+}
+
+/// `lo..hi`に含まれる素数を昇順に返す。
+///
+/// O(√hi + (hi - lo) log log hi)で計算するので、`hi`が`10^12`程度でも区間が短ければ高速に動く。
+pub fn segmented_sieve<T>(lo: T, hi: T) -> Vec<T>
+where
+    T: Integer + Copy + TryFrom<u64> + TryInto<u64>,
+{
+    const BLOCK: u64 = 1 << 16;
+    let zero = T::zero();
+    let (lo, hi) = (to_u64(lo.max(zero)).max(2), to_u64(hi.max(zero)));
+    if lo >= hi {
+        return Vec::new();
+    }
+    let base = LinearSieve::<u64>::new((hi - 1).isqrt() as usize);
+    let mut result = Vec::new();
+    let mut is_prime = vec![true; BLOCK as usize];
+    let mut start = lo;
+    while start < hi {
+        let end = hi.min(start + BLOCK);
+        let block = &mut is_prime[..(end - start) as usize];
+        block.fill(true);
+        for &p in base.primes() {
+            // `p`自身は消さないように`p^2`以上の倍数から消す
+            let first = (p * p).max(start.div_ceil(p) * p);
+            let mut m = first;
+            while m < end {
+                block[(m - start) as usize] = false;
+                m += p;
+            }
+        }
+        result.extend(
+            block
+                .iter()
+                .enumerate()
+                .filter(|&(_, &b)| b)
+                .map(|(i, _)| from_u64::<T>(start + i as u64)),
+        );
+        start = end;
+    }
+    result
+}
+
+/// `n`以下の素数の個数をLucy_Hedgehogのアルゴリズムを使い、O(n^(3/4))で求める。
+pub fn count_primes<T>(n: T) -> T
+where
+    T: Integer + Copy + TryFrom<u64> + TryInto<u64>,
+{
+    let n = to_u64(n);
+    if n < 2 {
+        return T::zero();
+    }
+    let r = n.isqrt() as usize;
+    // small[v]: `v`以下の数のうち篩に残っている数の個数
+    // large[i]: `n / i`以下の数のうち篩に残っている数の個数
+    let mut small = (0..=r as u64)
+        .map(|v| v.saturating_sub(1))
+        .collect::<Vec<_>>();
+    let mut large = (0..=r as u64)
+        .map(|i| n.checked_div(i).map_or(0, |v| v - 1))
+        .collect::<Vec<_>>();
+    for p in 2..=r {
+        if small[p] == small[p - 1] {
+            continue;
+        }
+        let sp = small[p - 1];
+        let p2 = (p * p) as u64;
+        let p = p as u64;
+        for i in 1..=(r as u64).min(n / p2) {
+            let d = i * p;
+            let s = if d <= r as u64 {
+                large[d as usize]
+            } else {
+                small[(n / d) as usize]
+            };
+            large[i as usize] -= s - sp;
+        }
+        for v in (p2 as usize..=r).rev() {
+            small[v] -= small[v / p as usize] - sp;
+        }
+    }
+    from_u64(large[1])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gcd;
+
+    fn is_prime_naive(n: u64) -> bool {
+        n >= 2
+            && (2..)
+                .take_while(|i| i * i <= n)
+                .all(|i| !n.is_multiple_of(i))
+    }
+
+    #[test]
+    fn linear_sieve_test() {
+        let sieve = LinearSieve::<u32>::new(1000);
+        assert_eq!(sieve.max(), 1000);
+        let expected = (0..=1000)
+            .filter(|&i| is_prime_naive(i))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            sieve.primes().iter().map(|&p| p as u64).collect::<Vec<_>>(),
+            expected
+        );
+        for i in 2..=1000u32 {
+            assert_eq!(sieve.is_prime(i), is_prime_naive(i as u64));
+            let spf = (2..=i).find(|p| i % p == 0).unwrap();
+            assert_eq!(sieve.smallest_prime_factor(i), spf);
+            let factors = sieve.factorize(i);
+            assert_eq!(factors.iter().map(|&(p, e)| p.pow(e)).product::<u32>(), i);
+        }
+        assert_eq!(sieve.factorize(360), vec![(2, 3), (3, 2), (5, 1)]);
+        assert_eq!(sieve.factorize(1), vec![]);
+        assert!(!sieve.is_prime(0));
+        assert!(!sieve.is_prime(1));
+
+        let sieve = LinearSieve::<i64>::new(1);
+        assert!(sieve.primes().is_empty());
+        assert_eq!(sieve.mobius_table(), vec![0, 1]);
+    }
+
+    #[test]
+    fn multiplicative_table_test() {
+        let n = 500;
+        let sieve = LinearSieve::<i32>::new(n);
+        let phi = sieve.euler_phi_table();
+        let d = sieve.divisor_count_table();
+        let mu = sieve.mobius_table();
+        assert_eq!(phi[0], 0);
+        assert_eq!(d[0], 0);
+        assert_eq!(mu[0], 0);
+        for i in 1..=n as i32 {
+            let expected_phi = (1..=i).filter(|&k| gcd(k, i) == 1).count() as i32;
+            assert_eq!(phi[i as usize], expected_phi);
+            let expected_d = (1..=i).filter(|&k| i % k == 0).count() as i32;
+            assert_eq!(d[i as usize], expected_d);
+            // Σ_{d | i} μ(d) = [i == 1]
+            let sum_mu = (1..=i)
+                .filter(|&k| i % k == 0)
+                .map(|k| mu[k as usize])
+                .sum::<i32>();
+            assert_eq!(sum_mu, (i == 1) as i32);
+        }
+        assert_eq!(&mu[1..11], &[1, -1, -1, 0, -1, 1, -1, 0, 0, 1]);
+    }
+
+    #[test]
+    fn segmented_sieve_test() {
+        let expected = (0..2000).filter(|&i| is_prime_naive(i)).collect::<Vec<_>>();
+        for (lo, hi) in [
+            (0u64, 2000u64),
+            (0, 2),
+            (2, 3),
+            (100, 200),
+            (1000, 1000),
+            (1500, 1000),
+        ] {
+            let expected = expected
+                .iter()
+                .copied()
+                .filter(|&p| lo <= p && p < hi)
+                .collect::<Vec<_>>();
+            assert_eq!(segmented_sieve(lo, hi), expected);
+        }
+        let lo = 1_000_000_000_000u64;
+        let primes = segmented_sieve(lo, lo + 1000);
+        assert_eq!(primes[0], 1_000_000_000_039);
+        assert_eq!(
+            primes,
+            (lo..lo + 1000)
+                .filter(|&i| is_prime_naive(i))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(segmented_sieve(lo, lo + 200_000).len(), 7243);
+        assert_eq!(segmented_sieve(-5i64, 12), vec![2, 3, 5, 7, 11]);
+    }
+
+    #[test]
+    fn count_primes_test() {
+        let mut count = 0;
+        for n in 0..2000u64 {
+            if is_prime_naive(n) {
+                count += 1;
+            }
+            assert_eq!(count_primes(n), count);
+        }
+        assert_eq!(count_primes(1_000_000u32), 78498);
+        assert_eq!(count_primes(1_000_000_000i64), 50847534);
+        assert_eq!(count_primes(10_000_000_000u64), 455052511);
+    }
+}
@@ -1,6 +1,11 @@
-use std::ops::{
-    Add, AddAssign, BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Div, DivAssign,
-    Mul, MulAssign, Rem, RemAssign, Shl, ShlAssign, Shr, ShrAssign, Sub, SubAssign,
+use std::{
+    cmp::Ordering,
+    fmt::{self, Debug, Display},
+    hash::Hash,
+    ops::{
+        Add, AddAssign, BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Div,
+        DivAssign, Mul, MulAssign, Rem, RemAssign, Shl, ShlAssign, Shr, ShrAssign, Sub, SubAssign,
+    },
 };
 
 pub trait Arithmetic:
@@ -64,11 +69,18 @@ macro_rules! impl_arithmetic_for_int {
 impl_arithmetic_for_int!(i8, i16, i32, i64, i128, isize);
 impl_arithmetic_for_int!(u8, u16, u32, u64, u128, usize);
 
-pub trait Signed: Integer {}
+pub trait Signed: Integer {
+    fn abs(self) -> Self;
+    /// 負なら`-1`、0なら`0`、正なら`1`
+    fn signum(self) -> Self;
+}
 
 pub trait Unsigned: Integer {}
 
-pub trait FixedWidth: Integer {}
+pub trait FixedWidth: Integer {
+    fn max_value() -> Self;
+    fn min_value() -> Self;
+}
 
 pub trait ExistsBiggerInt: Integer {
     type Twice: Integer;
@@ -93,11 +105,96 @@ macro_rules! impl_exists_bigger_int {
     }
 }
 
-pub trait Integer: Arithmetic + Ord {
+pub trait Integer: Arithmetic + Ord + Clone {
     fn zero() -> Self;
     fn one() -> Self;
-    fn max_value() -> Self;
-    fn min_value() -> Self;
+
+    /// 負の無限大方向に丸めた商。`rhs`が0の場合はパニックする。
+    fn floor_div(self, rhs: Self) -> Self {
+        let q = self.clone() / rhs.clone();
+        let r = self % rhs.clone();
+        if r != Self::zero() && (r < Self::zero()) != (rhs < Self::zero()) {
+            q - Self::one()
+        } else {
+            q
+        }
+    }
+
+    /// 正の無限大方向に丸めた商。`rhs`が0の場合はパニックする。
+    fn ceil_div(self, rhs: Self) -> Self {
+        let q = self.clone() / rhs.clone();
+        let r = self % rhs.clone();
+        if r != Self::zero() && (r < Self::zero()) == (rhs < Self::zero()) {
+            q + Self::one()
+        } else {
+            q
+        }
+    }
+
+    /// 平方根を0方向に丸めた値。負の場合はパニックする。
+    fn sqrt(self) -> Self {
+        self.nth_root(2)
+    }
+
+    /// 立方根を0方向に丸めた値
+    fn cbrt(self) -> Self {
+        self.nth_root(3)
+    }
+
+    /// `k`乗根を0方向に丸めた値。`k`が0の場合と、`k`が偶数で`self`が負の場合はパニックする。
+    fn nth_root(self, k: u32) -> Self {
+        assert!(k > 0, "k must be positive.");
+        let zero = Self::zero();
+        let one = Self::one();
+        if k == 1 || self >= zero {
+            return root_floor(self, k);
+        }
+        assert!(k % 2 == 1, "even root of a negative number.");
+        // `-self`はオーバーフローし得るので、`m = -(self + 1)`の根`s`から求める。
+        // 答えの絶対値は`s`か`s + 1`で、`(s + 1)^k = m + 1`のときに限り`s + 1`になる。
+        let m = zero.clone() - (self + one.clone());
+        let s = root_floor(m.clone(), k);
+        let p = s.clone() + one.clone();
+        let (q, r) = (m.clone() / p.clone(), m % p.clone());
+        let exact = r == p.clone() - one.clone()
+            && pow_cmp(p.clone(), k - 1, q + one.clone()) == Ordering::Equal;
+        zero - if exact { p } else { s }
+    }
+}
+
+/// `x^e`と`n`を比較する。`x >= 1`、`n >= 0`である必要があり、途中でオーバーフローしない。
+fn pow_cmp<T: Integer>(x: T, e: u32, n: T) -> Ordering {
+    let mut acc = T::one();
+    for _ in 0..e {
+        if acc > n.clone() / x.clone() {
+            return Ordering::Greater;
+        }
+        acc *= x.clone();
+    }
+    acc.cmp(&n)
+}
+
+/// 非負の`n`の`k`乗根の切り捨て
+fn root_floor<T: Integer>(n: T, k: u32) -> T {
+    let one = T::one();
+    if k == 1 || n <= one {
+        return n;
+    }
+    // lo^k <= n < hi^k を保って二分探索する
+    let mut hi = one.clone();
+    while pow_cmp(hi.clone(), k, n.clone()) != Ordering::Greater {
+        hi = hi.clone() + hi;
+    }
+    let mut lo = hi.clone() / (one.clone() + one.clone());
+    while hi.clone() - lo.clone() > one {
+        let mid = lo.clone() + (hi.clone() - lo.clone()) / (one.clone() + one.clone());
+        if pow_cmp(mid.clone(), k, n.clone()) == Ordering::Greater {
+            hi = mid;
+        } else {
+            lo = mid;
+        }
+    }
+    lo
 }
 
 macro_rules! impl_integer {
@@ -106,6 +203,9 @@ macro_rules! impl_integer {
             impl Integer for $t {
                 fn zero() -> Self { 0 }
                 fn one() -> Self { 1 }
+            }
+
+            impl FixedWidth for $t {
                 fn max_value() -> Self { <$t>::MAX }
                 fn min_value() -> Self { <$t>::MIN }
             }
@@ -113,6 +213,17 @@ macro_rules! impl_integer {
     };
 }
 
+macro_rules! impl_signed {
+    ($($t:ty),*) => {
+        $(
+            impl Signed for $t {
+                fn abs(self) -> Self { self.abs() }
+                fn signum(self) -> Self { self.signum() }
+            }
+        )*
+    };
+}
+
 macro_rules! impl_marker_trait {
     ($trait:ty, $($t:ty),*) => {
         $(
@@ -123,12 +234,10 @@ macro_rules! impl_marker_trait {
 
 impl_integer!(i8, i16, i32, i64, i128, isize);
 impl_integer!(u8, u16, u32, u64, u128, usize);
-impl_marker_trait!(Signed, i8, i16, i32, i64, i128, isize);
+impl_signed!(i8, i16, i32, i64, i128, isize);
 impl_marker_trait!(Unsigned, u8, u16, u32, u64, u128, usize);
-impl_marker_trait!(FixedWidth, i8, i16, i32, i64, i128, isize);
-impl_marker_trait!(FixedWidth, u8, u16, u32, u64, u128, usize);
-impl_exists_bigger_int!(i8: i16, i16: i32, i32: i64, i64: i128);
-impl_exists_bigger_int!(u8: u16, u16: u32, u32: u64, u64: u128);
+impl_exists_bigger_int!(i8: i16, i16: i32, i32: i64, i64: i128, isize: i128);
+impl_exists_bigger_int!(u8: u16, u16: u32, u32: u64, u64: u128, usize: u128);
 
 pub trait SaturatingOps: Arithmetic {
     fn saturating_add(self, rhs: Self) -> Self;
@@ -146,6 +255,11 @@ pub trait WrappingOps: Arithmetic {
     fn wrapping_neg(self) -> Self;
     fn wrapping_rem(self, rhs: Self) -> Self;
     fn wrapping_pow(self, exp: Self::BitWidthType) -> Self;
+    /// シフト量をビット幅で割った余りにしてシフトする。
+    fn wrapping_shl(self, rhs: Self::BitWidthType) -> Self;
+    fn wrapping_shr(self, rhs: Self::BitWidthType) -> Self;
+    fn wrapping_div_euclid(self, rhs: Self) -> Self;
+    fn wrapping_rem_euclid(self, rhs: Self) -> Self;
 }
 
 pub trait OverflowingOps: Arithmetic {
@@ -211,6 +325,18 @@ macro_rules! impl_ops {
                 fn wrapping_pow(self, exp: Self::BitWidthType) -> Self {
                     self.wrapping_pow(exp)
                 }
+                fn wrapping_shl(self, rhs: Self::BitWidthType) -> Self {
+                    self.wrapping_shl(rhs)
+                }
+                fn wrapping_shr(self, rhs: Self::BitWidthType) -> Self {
+                    self.wrapping_shr(rhs)
+                }
+                fn wrapping_div_euclid(self, rhs: Self) -> Self {
+                    self.wrapping_div_euclid(rhs)
+                }
+                fn wrapping_rem_euclid(self, rhs: Self) -> Self {
+                    self.wrapping_rem_euclid(rhs)
+                }
             }
 
             impl OverflowingOps for $t {
@@ -279,6 +405,59 @@ macro_rules! impl_ops {
 impl_ops!(i8, i16, i32, i64, i128, isize);
 impl_ops!(u8, u16, u32, u64, u128, usize);
 
+/// 固定幅の整数型。プリミティブ整数と、それを包む[`Wrapping`]、[`Saturating`]、[`Checked`]、[`Strict`]が実装する。
+pub trait PrimInt:
+    FixedWidth
+    + Arithmetic<BitWidthType = u32>
+    + BitArithmetic
+    + CheckedOps
+    + SaturatingOps
+    + WrappingOps
+    + OverflowingOps
+    + Copy
+    + Hash
+    + Debug
+    + Display
+{
+    /// ビット幅
+    const BITS: u32;
+    fn count_ones(self) -> u32;
+    fn count_zeros(self) -> u32;
+    fn leading_zeros(self) -> u32;
+    fn trailing_zeros(self) -> u32;
+    /// 平方根の切り捨て。負の場合はパニックする。
+    fn isqrt(self) -> Self;
+    /// `base`を底とする対数の切り捨て。`self`が正でない場合や`base < 2`の場合はパニックする。
+    fn ilog(self, base: Self) -> u32;
+    fn ilog2(self) -> u32;
+    fn ilog10(self) -> u32;
+}
+
+macro_rules! impl_prim_int {
+    ($($t:ty),*) => {
+        $(
+            impl PrimInt for $t {
+                const BITS: u32 = <$t>::BITS;
+                fn count_ones(self) -> u32 { self.count_ones() }
+                fn count_zeros(self) -> u32 { self.count_zeros() }
+                fn leading_zeros(self) -> u32 { self.leading_zeros() }
+                fn trailing_zeros(self) -> u32 { self.trailing_zeros() }
+                fn isqrt(self) -> Self { self.isqrt() }
+                fn ilog(self, base: Self) -> u32 { self.ilog(base) }
+                fn ilog2(self) -> u32 { self.ilog2() }
+                fn ilog10(self) -> u32 { self.ilog10() }
+            }
+        )*
+    };
+}
+
+impl_prim_int!(i8, i16, i32, i64, i128, isize);
+impl_prim_int!(u8, u16, u32, u64, u128, usize);
+
+/// 飽和演算を行う整数。
+///
+/// ビット幅以上のシフトは、すべてのビットを押し出したものとして扱う。
+/// `MIN.div_euclid(-1)`は`MAX`に飽和する。
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(transparent)]
 pub struct Saturating<T>(pub T);
@@ -497,6 +676,7 @@ where
     }
 }
 
+/// `2^BITS`を法として演算する整数。シフト量もビット幅で割った余りを使う。
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(transparent)]
 pub struct Wrapping<T>(pub T);
@@ -717,8 +897,1251 @@ where
     }
 }
 
+/// オーバーフローを検出する整数。一度オーバーフローすると、その後の演算結果も全てオーバーフロー扱いになる。
+///
+/// 0除算や、ビット幅以上のシフトもオーバーフローとして扱う。
+///
+/// **注意**: `Ord`は`Option`の順序をそのまま使うので、オーバーフローした値はどの値よりも小さいものとして比較される。
+/// そのため`std::cmp::max`、`Iterator::max`、`Ord::clamp`など比較に基づく処理では、オーバーフローが結果から消えうる。
+/// オーバーフローを伝播させたい場合は[`Checked::max`]、[`Checked::min`]、[`Checked::clamp`]を使う。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct Checked<T>(pub Option<T>);
+
+impl<T> Checked<T> {
+    pub fn new(value: T) -> Self {
+        Self(Some(value))
+    }
+
+    /// 途中でオーバーフローしていれば`None`
+    pub fn get(self) -> Option<T> {
+        self.0
+    }
+
+    pub fn is_overflowed(&self) -> bool {
+        self.0.is_none()
+    }
+
+    /// 値を取り出す。オーバーフローしていればパニックする。
+    #[track_caller]
+    fn unwrap_checked(self) -> T {
+        self.0.expect("attempt to use an overflowed value")
+    }
+}
+
+impl<T: Ord> Checked<T> {
+    /// どちらかがオーバーフローしていればオーバーフローした値を返す。
+    pub fn max(self, other: Self) -> Self {
+        Checked(self.0.zip(other.0).map(|(a, b)| a.max(b)))
+    }
+
+    /// どちらかがオーバーフローしていればオーバーフローした値を返す。
+    pub fn min(self, other: Self) -> Self {
+        Checked(self.0.zip(other.0).map(|(a, b)| a.min(b)))
+    }
+
+    /// いずれかがオーバーフローしていればオーバーフローした値を返す。
+    /// `min > max`の場合はパニックする。
+    pub fn clamp(self, min: Self, max: Self) -> Self {
+        Checked(
+            self.0
+                .zip(min.0)
+                .zip(max.0)
+                .map(|((x, lo), hi)| x.clamp(lo, hi)),
+        )
+    }
+}
+
+impl<T: CheckedOps> Checked<T> {
+    pub fn pow(self, exp: T::BitWidthType) -> Self {
+        Self(self.0.and_then(|x| x.checked_pow(exp)))
+    }
+}
+
+impl<T> From<T> for Checked<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T: Display> Display for Checked<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            Some(x) => x.fmt(f),
+            None => f.pad("overflow"),
+        }
+    }
+}
+
+macro_rules! impl_checked_ops {
+    ($($trait:ident, $f:ident, $trait_assign:ident, $f_assign:ident, $checked:ident);*) => {
+        $(
+            impl<T: CheckedOps> $trait for Checked<T> {
+                type Output = Self;
+
+                fn $f(self, rhs: Self) -> Self::Output {
+                    Checked(self.0.zip(rhs.0).and_then(|(a, b)| a.$checked(b)))
+                }
+            }
+
+            impl<T: CheckedOps> $trait<T> for Checked<T> {
+                type Output = Self;
+
+                fn $f(self, rhs: T) -> Self::Output {
+                    Checked(self.0.and_then(|a| a.$checked(rhs)))
+                }
+            }
+
+            impl<T: CheckedOps> $trait_assign for Checked<T> {
+                fn $f_assign(&mut self, rhs: Self) {
+                    let tmp = Checked(self.0.take()).$f(rhs);
+                    *self = tmp;
+                }
+            }
+
+            impl<T: CheckedOps> $trait_assign<T> for Checked<T> {
+                fn $f_assign(&mut self, rhs: T) {
+                    let tmp = Checked(self.0.take()).$f(rhs);
+                    *self = tmp;
+                }
+            }
+        )*
+    };
+}
+
+impl_checked_ops!(
+    Add, add, AddAssign, add_assign, checked_add;
+    Sub, sub, SubAssign, sub_assign, checked_sub;
+    Mul, mul, MulAssign, mul_assign, checked_mul;
+    Div, div, DivAssign, div_assign, checked_div;
+    Rem, rem, RemAssign, rem_assign, checked_rem
+);
+
+/// 両方ともオーバーフローしていなければ`f`を適用する。
+fn checked_zip<T>(lhs: Checked<T>, rhs: Checked<T>, f: impl FnOnce(T, T) -> T) -> Checked<T> {
+    Checked(lhs.0.zip(rhs.0).map(|(a, b)| f(a, b)))
+}
+
+/// 0をオーバーフローに置き換える。
+fn checked_nonzero<T: PrimInt>(value: Checked<T>) -> Checked<T> {
+    Checked(value.0.filter(|&x| x != T::zero()))
+}
+
+/// `OverflowingOps`の結果を`Checked`に包む。既にオーバーフローしていれば`true`を返す。
+fn checked_overflowing<T>(value: Option<(T, bool)>) -> (Checked<T>, bool) {
+    match value {
+        Some((x, o)) => (Checked::new(x), o),
+        None => (Checked(None), true),
+    }
+}
+
+/// 演算結果がオーバーフローしていなければ`Some`を返す。
+fn checked_filter<T>(value: Checked<T>) -> Option<Checked<T>> {
+    Some(value).filter(|x| !x.is_overflowed())
+}
+
+impl<T: PrimInt> Arithmetic for Checked<T> {
+    type BitWidthType = T::BitWidthType;
+    fn pow(self, exp: Self::BitWidthType) -> Self {
+        Checked(self.0.and_then(|x| x.checked_pow(exp)))
+    }
+
+    fn div_euclid(self, rhs: Self) -> Self {
+        // `div_euclid`がオーバーフローするのは、`checked_div`が失敗するときに限る
+        Checked(
+            self.0
+                .zip(rhs.0)
+                .and_then(|(a, b)| a.checked_div(b).map(|_| a.div_euclid(b))),
+        )
+    }
+
+    fn rem_euclid(self, rhs: Self) -> Self {
+        Checked(
+            self.0
+                .zip(rhs.0)
+                .and_then(|(a, b)| a.checked_rem(b).map(|_| a.rem_euclid(b))),
+        )
+    }
+}
+
+impl<T: PrimInt> Integer for Checked<T> {
+    fn zero() -> Self {
+        Checked::new(T::zero())
+    }
+
+    fn one() -> Self {
+        Checked::new(T::one())
+    }
+}
+
+impl<T: PrimInt> FixedWidth for Checked<T> {
+    fn max_value() -> Self {
+        Checked::new(T::max_value())
+    }
+
+    fn min_value() -> Self {
+        Checked::new(T::min_value())
+    }
+}
+
+impl<T: PrimInt + Signed> Signed for Checked<T> {
+    fn abs(self) -> Self {
+        Checked(self.0.and_then(|x| {
+            if x < T::zero() {
+                x.checked_neg()
+            } else {
+                Some(x)
+            }
+        }))
+    }
+
+    fn signum(self) -> Self {
+        Checked(self.0.map(Signed::signum))
+    }
+}
+
+impl<T: PrimInt + Unsigned> Unsigned for Checked<T> {}
+
+impl<T: PrimInt> BitAnd for Checked<T> {
+    type Output = Self;
+    fn bitand(self, rhs: Self) -> Self {
+        checked_zip(self, rhs, |a, b| a & b)
+    }
+}
+
+impl<T: PrimInt> BitOr for Checked<T> {
+    type Output = Self;
+    fn bitor(self, rhs: Self) -> Self {
+        checked_zip(self, rhs, |a, b| a | b)
+    }
+}
+
+impl<T: PrimInt> BitXor for Checked<T> {
+    type Output = Self;
+    fn bitxor(self, rhs: Self) -> Self {
+        checked_zip(self, rhs, |a, b| a ^ b)
+    }
+}
+
+impl<T: PrimInt> Shl<T::BitWidthType> for Checked<T> {
+    type Output = Self;
+    fn shl(self, rhs: T::BitWidthType) -> Self {
+        Checked(self.0.filter(|_| rhs < T::BITS).map(|x| x << rhs))
+    }
+}
+
+impl<T: PrimInt> Shr<T::BitWidthType> for Checked<T> {
+    type Output = Self;
+    fn shr(self, rhs: T::BitWidthType) -> Self {
+        Checked(self.0.filter(|_| rhs < T::BITS).map(|x| x >> rhs))
+    }
+}
+
+impl<T: PrimInt> BitAndAssign for Checked<T> {
+    fn bitand_assign(&mut self, rhs: Self) {
+        *self = *self & rhs;
+    }
+}
+
+impl<T: PrimInt> BitOrAssign for Checked<T> {
+    fn bitor_assign(&mut self, rhs: Self) {
+        *self = *self | rhs;
+    }
+}
+
+impl<T: PrimInt> BitXorAssign for Checked<T> {
+    fn bitxor_assign(&mut self, rhs: Self) {
+        *self = *self ^ rhs;
+    }
+}
+
+impl<T: PrimInt> ShlAssign<T::BitWidthType> for Checked<T> {
+    fn shl_assign(&mut self, rhs: T::BitWidthType) {
+        *self = *self << rhs;
+    }
+}
+
+impl<T: PrimInt> ShrAssign<T::BitWidthType> for Checked<T> {
+    fn shr_assign(&mut self, rhs: T::BitWidthType) {
+        *self = *self >> rhs;
+    }
+}
+
+impl<T: PrimInt> BitArithmetic for Checked<T> {}
+
+impl<T: PrimInt> SaturatingOps for Checked<T> {
+    fn saturating_add(self, rhs: Self) -> Self {
+        checked_zip(self, rhs, T::saturating_add)
+    }
+    fn saturating_sub(self, rhs: Self) -> Self {
+        checked_zip(self, rhs, T::saturating_sub)
+    }
+    fn saturating_mul(self, rhs: Self) -> Self {
+        checked_zip(self, rhs, T::saturating_mul)
+    }
+    fn saturating_div(self, rhs: Self) -> Self {
+        checked_zip(self, checked_nonzero(rhs), T::saturating_div)
+    }
+    fn saturating_pow(self, exp: Self::BitWidthType) -> Self {
+        Checked(self.0.map(|x| x.saturating_pow(exp)))
+    }
+}
+
+impl<T: PrimInt> WrappingOps for Checked<T> {
+    fn wrapping_add(self, rhs: Self) -> Self {
+        checked_zip(self, rhs, T::wrapping_add)
+    }
+    fn wrapping_sub(self, rhs: Self) -> Self {
+        checked_zip(self, rhs, T::wrapping_sub)
+    }
+    fn wrapping_mul(self, rhs: Self) -> Self {
+        checked_zip(self, rhs, T::wrapping_mul)
+    }
+    fn wrapping_div(self, rhs: Self) -> Self {
+        checked_zip(self, checked_nonzero(rhs), T::wrapping_div)
+    }
+    fn wrapping_neg(self) -> Self {
+        Checked(self.0.map(T::wrapping_neg))
+    }
+    fn wrapping_rem(self, rhs: Self) -> Self {
+        checked_zip(self, checked_nonzero(rhs), T::wrapping_rem)
+    }
+    fn wrapping_pow(self, exp: Self::BitWidthType) -> Self {
+        Checked(self.0.map(|x| x.wrapping_pow(exp)))
+    }
+    fn wrapping_shl(self, rhs: Self::BitWidthType) -> Self {
+        Checked(self.0.map(|x| x.wrapping_shl(rhs)))
+    }
+    fn wrapping_shr(self, rhs: Self::BitWidthType) -> Self {
+        Checked(self.0.map(|x| x.wrapping_shr(rhs)))
+    }
+    fn wrapping_div_euclid(self, rhs: Self) -> Self {
+        checked_zip(self, checked_nonzero(rhs), T::wrapping_div_euclid)
+    }
+    fn wrapping_rem_euclid(self, rhs: Self) -> Self {
+        checked_zip(self, checked_nonzero(rhs), T::wrapping_rem_euclid)
+    }
+}
+
+impl<T: PrimInt> OverflowingOps for Checked<T> {
+    fn overflowing_add(self, rhs: Self) -> (Self, bool) {
+        checked_overflowing(self.0.zip(rhs.0).map(|(a, b)| a.overflowing_add(b)))
+    }
+    fn overflowing_sub(self, rhs: Self) -> (Self, bool) {
+        checked_overflowing(self.0.zip(rhs.0).map(|(a, b)| a.overflowing_sub(b)))
+    }
+    fn overflowing_mul(self, rhs: Self) -> (Self, bool) {
+        checked_overflowing(self.0.zip(rhs.0).map(|(a, b)| a.overflowing_mul(b)))
+    }
+    fn overflowing_div(self, rhs: Self) -> (Self, bool) {
+        checked_overflowing(
+            self.0
+                .zip(checked_nonzero(rhs).0)
+                .map(|(a, b)| a.overflowing_div(b)),
+        )
+    }
+    fn overflowing_neg(self) -> (Self, bool) {
+        checked_overflowing(self.0.map(T::overflowing_neg))
+    }
+    fn overflowing_rem(self, rhs: Self) -> (Self, bool) {
+        checked_overflowing(
+            self.0
+                .zip(checked_nonzero(rhs).0)
+                .map(|(a, b)| a.overflowing_rem(b)),
+        )
+    }
+    fn overflowing_pow(self, exp: Self::BitWidthType) -> (Self, bool) {
+        checked_overflowing(self.0.map(|x| x.overflowing_pow(exp)))
+    }
+}
+
+impl<T: PrimInt> CheckedOps for Checked<T> {
+    fn checked_add(self, rhs: Self) -> Option<Self> {
+        checked_filter(self + rhs)
+    }
+    fn checked_sub(self, rhs: Self) -> Option<Self> {
+        checked_filter(self - rhs)
+    }
+    fn checked_mul(self, rhs: Self) -> Option<Self> {
+        checked_filter(self * rhs)
+    }
+    fn checked_div(self, rhs: Self) -> Option<Self> {
+        checked_filter(self / rhs)
+    }
+    fn checked_neg(self) -> Option<Self> {
+        checked_filter(Checked(self.0.and_then(T::checked_neg)))
+    }
+    fn checked_rem(self, rhs: Self) -> Option<Self> {
+        checked_filter(self % rhs)
+    }
+    fn checked_pow(self, exp: Self::BitWidthType) -> Option<Self> {
+        checked_filter(Arithmetic::pow(self, exp))
+    }
+}
+
+/// 値を取り出せない演算は、オーバーフローしていればパニックする。
+impl<T: PrimInt> PrimInt for Checked<T> {
+    const BITS: u32 = T::BITS;
+    fn count_ones(self) -> u32 {
+        self.unwrap_checked().count_ones()
+    }
+    fn count_zeros(self) -> u32 {
+        self.unwrap_checked().count_zeros()
+    }
+    fn leading_zeros(self) -> u32 {
+        self.unwrap_checked().leading_zeros()
+    }
+    fn trailing_zeros(self) -> u32 {
+        self.unwrap_checked().trailing_zeros()
+    }
+    fn isqrt(self) -> Self {
+        Checked(self.0.map(T::isqrt))
+    }
+    fn ilog(self, base: Self) -> u32 {
+        self.unwrap_checked().ilog(base.unwrap_checked())
+    }
+    fn ilog2(self) -> u32 {
+        self.unwrap_checked().ilog2()
+    }
+    fn ilog10(self) -> u32 {
+        self.unwrap_checked().ilog10()
+    }
+}
+
+/// オーバーフローした時点で、ビルドの設定に関わらずパニックする整数。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct Strict<T>(pub T);
+
+#[track_caller]
+fn unwrap_strict<T>(value: Option<T>, message: &str) -> T {
+    match value {
+        Some(x) => x,
+        None => panic!("{message}"),
+    }
+}
+
+impl<T: CheckedOps> Strict<T> {
+    #[track_caller]
+    pub fn pow(self, exp: T::BitWidthType) -> Self {
+        Self(unwrap_strict(
+            self.0.checked_pow(exp),
+            "attempt to exponentiate with overflow",
+        ))
+    }
+}
+
+/// [`Strict`]の、四則演算以外でオーバーフローしうる演算
+trait StrictOps: PrimInt {
+    #[track_caller]
+    fn strict_pow(self, exp: u32) -> Self {
+        unwrap_strict(
+            self.checked_pow(exp),
+            "attempt to exponentiate with overflow",
+        )
+    }
+
+    #[track_caller]
+    fn strict_sub(self, rhs: Self) -> Self {
+        unwrap_strict(self.checked_sub(rhs), "attempt to subtract with overflow")
+    }
+
+    #[track_caller]
+    fn strict_shl(self, rhs: u32) -> Self {
+        assert!(rhs < Self::BITS, "attempt to shift left with overflow");
+        self << rhs
+    }
+
+    #[track_caller]
+    fn strict_shr(self, rhs: u32) -> Self {
+        assert!(rhs < Self::BITS, "attempt to shift right with overflow");
+        self >> rhs
+    }
+}
+
+impl<T: PrimInt> StrictOps for T {}
+
+/// [`Saturating`]の、四則演算以外でオーバーフローしうる演算
+trait SaturatingExtOps: PrimInt {
+    /// `rhs`がビット幅以上なら0
+    fn unbounded_shl(self, rhs: u32) -> Self {
+        if rhs < Self::BITS {
+            self << rhs
+        } else {
+            Self::zero()
+        }
+    }
+
+    /// `rhs`がビット幅以上なら、符号に応じて0か-1
+    fn unbounded_shr(self, rhs: u32) -> Self {
+        if rhs < Self::BITS {
+            self >> rhs
+        } else {
+            self >> (Self::BITS - 1) >> 1
+        }
+    }
+
+    /// `rhs`が0の場合はパニックする。
+    fn saturating_div_euclid(self, rhs: Self) -> Self {
+        match self.checked_div(rhs) {
+            Some(_) => self.div_euclid(rhs),
+            // `MIN / -1`は割り切れるので、切り捨ての商と一致する
+            None => self.saturating_div(rhs),
+        }
+    }
+}
+
+impl<T: PrimInt> SaturatingExtOps for T {}
+
+macro_rules! impl_strict_ops {
+    ($($trait:ident, $f:ident, $trait_assign:ident, $f_assign:ident, $checked:ident, $message:literal);*) => {
+        $(
+            impl<T: CheckedOps> $trait for Strict<T> {
+                type Output = Self;
+
+                #[track_caller]
+                fn $f(self, rhs: Self) -> Self::Output {
+                    Strict(unwrap_strict(self.0.$checked(rhs.0), $message))
+                }
+            }
+
+            impl<T: CheckedOps> $trait<T> for Strict<T> {
+                type Output = Self;
+
+                #[track_caller]
+                fn $f(self, rhs: T) -> Self::Output {
+                    Strict(unwrap_strict(self.0.$checked(rhs), $message))
+                }
+            }
+
+            impl<T: CheckedOps + Clone> $trait_assign for Strict<T> {
+                #[track_caller]
+                fn $f_assign(&mut self, rhs: Self) {
+                    let tmp = self.clone().$f(rhs);
+                    *self = tmp;
+                }
+            }
+
+            impl<T: CheckedOps + Clone> $trait_assign<T> for Strict<T> {
+                #[track_caller]
+                fn $f_assign(&mut self, rhs: T) {
+                    let tmp = self.clone().$f(rhs);
+                    *self = tmp;
+                }
+            }
+        )*
+    };
+}
+
+impl_strict_ops!(
+    Add, add, AddAssign, add_assign, checked_add, "attempt to add with overflow";
+    Sub, sub, SubAssign, sub_assign, checked_sub, "attempt to subtract with overflow";
+    Mul, mul, MulAssign, mul_assign, checked_mul, "attempt to multiply with overflow";
+    Div, div, DivAssign, div_assign, checked_div, "attempt to divide by zero or with overflow";
+    Rem, rem, RemAssign, rem_assign, checked_rem,
+        "attempt to calculate the remainder with a divisor of zero or with overflow"
+);
+
+/// `Wrapping`、`Saturating`、`Strict`に整数の階層を実装する。
+/// 四則演算以外の演算は、それぞれの名前の付いた演算に対応させる。
+macro_rules! impl_wrapper {
+    (
+        $w:ident,
+        $pow:ident,
+        $abs_sub:ident,
+        $shl:ident,
+        $shr:ident,
+        $div_euclid:ident,
+        $rem_euclid:ident
+    ) => {
+        impl<T: PrimInt> Arithmetic for $w<T> {
+            type BitWidthType = T::BitWidthType;
+            #[track_caller]
+            fn pow(self, exp: Self::BitWidthType) -> Self {
+                $w(self.0.$pow(exp))
+            }
+
+            #[track_caller]
+            fn div_euclid(self, rhs: Self) -> Self {
+                $w(self.0.$div_euclid(rhs.0))
+            }
+
+            #[track_caller]
+            fn rem_euclid(self, rhs: Self) -> Self {
+                $w(self.0.$rem_euclid(rhs.0))
+            }
+        }
+
+        impl<T: PrimInt> Integer for $w<T> {
+            fn zero() -> Self {
+                $w(T::zero())
+            }
+
+            fn one() -> Self {
+                $w(T::one())
+            }
+        }
+
+        impl<T: PrimInt> FixedWidth for $w<T> {
+            fn max_value() -> Self {
+                $w(T::max_value())
+            }
+
+            fn min_value() -> Self {
+                $w(T::min_value())
+            }
+        }
+
+        impl<T: PrimInt + Signed> Signed for $w<T> {
+            #[track_caller]
+            fn abs(self) -> Self {
+                if self.0 < T::zero() {
+                    $w(T::zero().$abs_sub(self.0))
+                } else {
+                    self
+                }
+            }
+
+            fn signum(self) -> Self {
+                $w(self.0.signum())
+            }
+        }
+
+        impl<T: PrimInt + Unsigned> Unsigned for $w<T> {}
+
+        impl<T: PrimInt> BitAnd for $w<T> {
+            type Output = Self;
+            fn bitand(self, rhs: Self) -> Self {
+                $w(self.0 & rhs.0)
+            }
+        }
+
+        impl<T: PrimInt> BitOr for $w<T> {
+            type Output = Self;
+            fn bitor(self, rhs: Self) -> Self {
+                $w(self.0 | rhs.0)
+            }
+        }
+
+        impl<T: PrimInt> BitXor for $w<T> {
+            type Output = Self;
+            fn bitxor(self, rhs: Self) -> Self {
+                $w(self.0 ^ rhs.0)
+            }
+        }
+
+        impl<T: PrimInt> Shl<T::BitWidthType> for $w<T> {
+            type Output = Self;
+            #[track_caller]
+            fn shl(self, rhs: T::BitWidthType) -> Self {
+                $w(self.0.$shl(rhs))
+            }
+        }
+
+        impl<T: PrimInt> Shr<T::BitWidthType> for $w<T> {
+            type Output = Self;
+            #[track_caller]
+            fn shr(self, rhs: T::BitWidthType) -> Self {
+                $w(self.0.$shr(rhs))
+            }
+        }
+
+        impl<T: PrimInt> BitAndAssign for $w<T> {
+            fn bitand_assign(&mut self, rhs: Self) {
+                *self = *self & rhs;
+            }
+        }
+
+        impl<T: PrimInt> BitOrAssign for $w<T> {
+            fn bitor_assign(&mut self, rhs: Self) {
+                *self = *self | rhs;
+            }
+        }
+
+        impl<T: PrimInt> BitXorAssign for $w<T> {
+            fn bitxor_assign(&mut self, rhs: Self) {
+                *self = *self ^ rhs;
+            }
+        }
+
+        impl<T: PrimInt> ShlAssign<T::BitWidthType> for $w<T> {
+            #[track_caller]
+            fn shl_assign(&mut self, rhs: T::BitWidthType) {
+                *self = *self << rhs;
+            }
+        }
+
+        impl<T: PrimInt> ShrAssign<T::BitWidthType> for $w<T> {
+            #[track_caller]
+            fn shr_assign(&mut self, rhs: T::BitWidthType) {
+                *self = *self >> rhs;
+            }
+        }
+
+        impl<T: PrimInt> BitArithmetic for $w<T> {}
+
+        impl<T: PrimInt> SaturatingOps for $w<T> {
+            fn saturating_add(self, rhs: Self) -> Self {
+                $w(self.0.saturating_add(rhs.0))
+            }
+            fn saturating_sub(self, rhs: Self) -> Self {
+                $w(self.0.saturating_sub(rhs.0))
+            }
+            fn saturating_mul(self, rhs: Self) -> Self {
+                $w(self.0.saturating_mul(rhs.0))
+            }
+            fn saturating_div(self, rhs: Self) -> Self {
+                $w(self.0.saturating_div(rhs.0))
+            }
+            fn saturating_pow(self, exp: Self::BitWidthType) -> Self {
+                $w(self.0.saturating_pow(exp))
+            }
+        }
+
+        impl<T: PrimInt> WrappingOps for $w<T> {
+            fn wrapping_add(self, rhs: Self) -> Self {
+                $w(self.0.wrapping_add(rhs.0))
+            }
+            fn wrapping_sub(self, rhs: Self) -> Self {
+                $w(self.0.wrapping_sub(rhs.0))
+            }
+            fn wrapping_mul(self, rhs: Self) -> Self {
+                $w(self.0.wrapping_mul(rhs.0))
+            }
+            fn wrapping_div(self, rhs: Self) -> Self {
+                $w(self.0.wrapping_div(rhs.0))
+            }
+            fn wrapping_neg(self) -> Self {
+                $w(self.0.wrapping_neg())
+            }
+            fn wrapping_rem(self, rhs: Self) -> Self {
+                $w(self.0.wrapping_rem(rhs.0))
+            }
+            fn wrapping_pow(self, exp: Self::BitWidthType) -> Self {
+                $w(self.0.wrapping_pow(exp))
+            }
+            fn wrapping_shl(self, rhs: Self::BitWidthType) -> Self {
+                $w(self.0.wrapping_shl(rhs))
+            }
+            fn wrapping_shr(self, rhs: Self::BitWidthType) -> Self {
+                $w(self.0.wrapping_shr(rhs))
+            }
+            fn wrapping_div_euclid(self, rhs: Self) -> Self {
+                $w(self.0.wrapping_div_euclid(rhs.0))
+            }
+            fn wrapping_rem_euclid(self, rhs: Self) -> Self {
+                $w(self.0.wrapping_rem_euclid(rhs.0))
+            }
+        }
+
+        impl<T: PrimInt> OverflowingOps for $w<T> {
+            fn overflowing_add(self, rhs: Self) -> (Self, bool) {
+                let (x, o) = self.0.overflowing_add(rhs.0);
+                ($w(x), o)
+            }
+            fn overflowing_sub(self, rhs: Self) -> (Self, bool) {
+                let (x, o) = self.0.overflowing_sub(rhs.0);
+                ($w(x), o)
+            }
+            fn overflowing_mul(self, rhs: Self) -> (Self, bool) {
+                let (x, o) = self.0.overflowing_mul(rhs.0);
+                ($w(x), o)
+            }
+            fn overflowing_div(self, rhs: Self) -> (Self, bool) {
+                let (x, o) = self.0.overflowing_div(rhs.0);
+                ($w(x), o)
+            }
+            fn overflowing_neg(self) -> (Self, bool) {
+                let (x, o) = self.0.overflowing_neg();
+                ($w(x), o)
+            }
+            fn overflowing_rem(self, rhs: Self) -> (Self, bool) {
+                let (x, o) = self.0.overflowing_rem(rhs.0);
+                ($w(x), o)
+            }
+            fn overflowing_pow(self, exp: Self::BitWidthType) -> (Self, bool) {
+                let (x, o) = self.0.overflowing_pow(exp);
+                ($w(x), o)
+            }
+        }
+
+        impl<T: PrimInt> CheckedOps for $w<T> {
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                self.0.checked_add(rhs.0).map($w)
+            }
+            fn checked_sub(self, rhs: Self) -> Option<Self> {
+                self.0.checked_sub(rhs.0).map($w)
+            }
+            fn checked_mul(self, rhs: Self) -> Option<Self> {
+                self.0.checked_mul(rhs.0).map($w)
+            }
+            fn checked_div(self, rhs: Self) -> Option<Self> {
+                self.0.checked_div(rhs.0).map($w)
+            }
+            fn checked_neg(self) -> Option<Self> {
+                self.0.checked_neg().map($w)
+            }
+            fn checked_rem(self, rhs: Self) -> Option<Self> {
+                self.0.checked_rem(rhs.0).map($w)
+            }
+            fn checked_pow(self, exp: Self::BitWidthType) -> Option<Self> {
+                self.0.checked_pow(exp).map($w)
+            }
+        }
+
+        impl<T: Display> Display for $w<T> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.0.fmt(f)
+            }
+        }
+
+        impl<T: PrimInt> PrimInt for $w<T> {
+            const BITS: u32 = T::BITS;
+            fn count_ones(self) -> u32 {
+                self.0.count_ones()
+            }
+            fn count_zeros(self) -> u32 {
+                self.0.count_zeros()
+            }
+            fn leading_zeros(self) -> u32 {
+                self.0.leading_zeros()
+            }
+            fn trailing_zeros(self) -> u32 {
+                self.0.trailing_zeros()
+            }
+            fn isqrt(self) -> Self {
+                $w(self.0.isqrt())
+            }
+            fn ilog(self, base: Self) -> u32 {
+                self.0.ilog(base.0)
+            }
+            fn ilog2(self) -> u32 {
+                self.0.ilog2()
+            }
+            fn ilog10(self) -> u32 {
+                self.0.ilog10()
+            }
+        }
+    };
+}
+
+impl_wrapper!(
+    Saturating,
+    saturating_pow,
+    saturating_sub,
+    unbounded_shl,
+    unbounded_shr,
+    saturating_div_euclid,
+    wrapping_rem_euclid
+);
+impl_wrapper!(
+    Wrapping,
+    wrapping_pow,
+    wrapping_sub,
+    wrapping_shl,
+    wrapping_shr,
+    wrapping_div_euclid,
+    wrapping_rem_euclid
+);
+impl_wrapper!(Strict, strict_pow, strict_sub, strict_shl, strict_shr, div_euclid, rem_euclid);
+
 #[cfg(test)]
 mod tests {
+    use super::*;
+
+    fn generic_sum<T: PrimInt>(xs: &[T]) -> T {
+        xs.iter().fold(T::zero(), |acc, &x| acc + x)
+    }
+
+    fn popcount_sum<T: PrimInt>(xs: &[T]) -> u32 {
+        xs.iter().map(|&x| x.count_ones()).sum()
+    }
+
+    #[test]
+    fn prim_int_test() {
+        assert_eq!(generic_sum(&[1u8, 2, 3]), 6);
+        assert_eq!(generic_sum(&[Wrapping(200u8), Wrapping(100)]), Wrapping(44));
+        assert_eq!(
+            generic_sum(&[Saturating(200u8), Saturating(100)]),
+            Saturating(255)
+        );
+        assert_eq!(popcount_sum(&[Wrapping(7u32), Wrapping(8)]), 4);
+        assert_eq!(<Wrapping<i16> as PrimInt>::BITS, 16);
+        assert_eq!(PrimInt::isqrt(Saturating(99u64)), Saturating(9));
+        assert_eq!(PrimInt::ilog(Wrapping(1000u32), Wrapping(10)), 3);
+        assert_eq!(PrimInt::leading_zeros(Wrapping(1u64)), 63);
+        assert_eq!(PrimInt::trailing_zeros(Saturating(8i32)), 3);
+        assert_eq!(Wrapping(0b1100u8) & Wrapping(0b1010), Wrapping(0b1000));
+        assert_eq!(Saturating(1u32) << 4, Saturating(16));
+        assert_eq!(Wrapping::<i8>::max_value(), Wrapping(127));
+        assert_eq!(Wrapping(3u8).pow(6), Wrapping(217));
+        assert_eq!(Arithmetic::pow(Saturating(3u8), 6), Saturating(255));
+        assert_eq!(Wrapping(-7i32).rem_euclid(Wrapping(3)), Wrapping(2));
+        assert_eq!(Wrapping(250u8).checked_add(Wrapping(10)), None);
+        assert_eq!(Saturating(-5i32).to_string(), "-5");
+    }
+
+    #[test]
+    fn wrapper_shift_div_test() {
+        assert_eq!(Wrapping(1u32) << 40, Wrapping(1 << 8));
+        assert_eq!(Wrapping(-256i32) >> 36, Wrapping(-16));
+        let mut x = Wrapping(1u8);
+        x <<= 9;
+        assert_eq!(x, Wrapping(2));
+        assert_eq!(Wrapping(i32::MIN) / Wrapping(-1), Wrapping(i32::MIN));
+        assert_eq!(
+            Wrapping(i32::MIN).div_euclid(Wrapping(-1)),
+            Wrapping(i32::MIN)
+        );
+        assert_eq!(Wrapping(i32::MIN).rem_euclid(Wrapping(-1)), Wrapping(0));
+        assert_eq!(Wrapping(-7i32).div_euclid(Wrapping(2)), Wrapping(-4));
+        assert_eq!(1u8.wrapping_shl(9), 2);
+        assert_eq!(WrappingOps::wrapping_div_euclid(i8::MIN, -1), i8::MIN);
+        assert_eq!(Checked::new(1u8).wrapping_shl(9), Checked::new(2));
+        assert!(Checked::new(1i32)
+            .wrapping_rem_euclid(Checked::new(0))
+            .is_overflowed());
+
+        assert_eq!(Saturating(1u32) << 40, Saturating(0));
+        assert_eq!(Saturating(1u32) << 32, Saturating(0));
+        assert_eq!(Saturating(1u32) << 31, Saturating(1 << 31));
+        assert_eq!(Saturating(u32::MAX) >> 32, Saturating(0));
+        assert_eq!(Saturating(-256i32) >> 40, Saturating(-1));
+        assert_eq!(Saturating(256i32) >> 40, Saturating(0));
+        let mut x = Saturating(-1i8);
+        x <<= 8;
+        assert_eq!(x, Saturating(0));
+        assert_eq!(Saturating(i32::MIN) / Saturating(-1), Saturating(i32::MAX));
+        assert_eq!(
+            Saturating(i32::MIN).div_euclid(Saturating(-1)),
+            Saturating(i32::MAX)
+        );
+        assert_eq!(
+            Saturating(i32::MIN).rem_euclid(Saturating(-1)),
+            Saturating(0)
+        );
+        assert_eq!(Saturating(-7i32).div_euclid(Saturating(2)), Saturating(-4));
+        assert_eq!(Saturating(-7i32).rem_euclid(Saturating(-2)), Saturating(1));
+    }
+
     #[test]
-    fn check() {}
+    #[should_panic]
+    fn saturating_div_euclid_by_zero() {
+        let _ = Saturating(1i32).div_euclid(Saturating(0));
+    }
+
+    #[test]
+    fn signed_test() {
+        assert_eq!(Signed::abs(-5i32), 5);
+        assert_eq!(Signed::signum(-5i64), -1);
+        assert_eq!(Signed::signum(0i8), 0);
+        assert_eq!(Signed::abs(Saturating(i8::MIN)), Saturating(i8::MAX));
+        assert_eq!(Signed::abs(Wrapping(i8::MIN)), Wrapping(i8::MIN));
+        assert_eq!(Signed::abs(Wrapping(-3i8)), Wrapping(3));
+        assert_eq!(Signed::signum(Saturating(42i32)), Saturating(1));
+    }
+
+    #[test]
+    fn checked_test() {
+        let x = Checked::new(200u8);
+        assert_eq!((x + 55).get(), Some(255));
+        assert!((x + 56).is_overflowed());
+        // 一度オーバーフローすると戻らない
+        assert!((x + 56 - 100).is_overflowed());
+        assert!((x * Checked::new(2) / Checked::new(4)).is_overflowed());
+        assert!((x / 0).is_overflowed());
+        assert!((Checked::new(i32::MIN) % Checked::new(-1)).is_overflowed());
+        assert_eq!(Checked::new(3i64).pow(39).get(), Some(3i64.pow(39)));
+        assert!(Checked::new(3i64).pow(40).is_overflowed());
+        let mut y = Checked::new(10i32);
+        y -= 20;
+        y *= Checked::new(3);
+        y %= 7;
+        assert_eq!(y, Checked::new(-30 % 7));
+        y += i32::MAX;
+        y += i32::MAX;
+        assert_eq!(y.get(), None);
+        assert_eq!(y.to_string(), "overflow");
+        assert_eq!(Checked::new(-5i8).to_string(), "-5");
+        let sum = [100u8, 100, 100]
+            .iter()
+            .fold(Checked::new(0u8), |acc, &x| acc + x);
+        assert!(sum.is_overflowed());
+    }
+
+    #[test]
+    fn checked_prim_int_test() {
+        assert_eq!(
+            generic_sum(&[Checked::new(100u8), Checked::new(100)]),
+            Checked::new(200)
+        );
+        assert!(generic_sum(&[Checked::new(200u8), Checked::new(100)]).is_overflowed());
+        assert_eq!(popcount_sum(&[Checked::new(7u32), Checked::new(8)]), 4);
+        assert_eq!(<Checked<i16> as PrimInt>::BITS, 16);
+        assert_eq!(PrimInt::isqrt(Checked::new(99u64)), Checked::new(9));
+        assert_eq!(Integer::sqrt(Checked::new(99u64)), Checked::new(9));
+        assert_eq!(
+            Checked::new(0b1100u8) ^ Checked::new(0b1010),
+            Checked::new(0b0110)
+        );
+        assert_eq!(Checked::new(1u32) << 31, Checked::new(1 << 31));
+        assert!((Checked::new(1u32) << 32).is_overflowed());
+        assert!((Checked::new(1u32) >> 32).is_overflowed());
+        let mut x = Checked::new(0b1111u8);
+        x &= Checked::new(0b0110);
+        x |= Checked::new(0b1000);
+        x >>= 1;
+        assert_eq!(x, Checked::new(0b0111));
+        x <<= 8;
+        assert!(x.is_overflowed());
+        assert_eq!(Arithmetic::pow(Checked::new(3u8), 5), Checked::new(243));
+        assert_eq!(
+            Checked::new(-7i32).div_euclid(Checked::new(2)),
+            Checked::new(-4)
+        );
+        assert_eq!(
+            Checked::new(-7i32).rem_euclid(Checked::new(2)),
+            Checked::new(1)
+        );
+        assert!(Checked::new(1i32)
+            .div_euclid(Checked::new(0))
+            .is_overflowed());
+        assert!(Checked::new(i32::MIN)
+            .rem_euclid(Checked::new(-1))
+            .is_overflowed());
+        assert_eq!(Signed::abs(Checked::new(-5i8)), Checked::new(5));
+        assert!(Signed::abs(Checked::new(i8::MIN)).is_overflowed());
+        assert_eq!(Signed::signum(Checked::new(-5i8)), Checked::new(-1));
+        assert_eq!(Checked::<u8>::max_value(), Checked::new(255));
+        assert_eq!(
+            Checked::new(250u8).saturating_add(Checked::new(10)),
+            Checked::new(255)
+        );
+        assert_eq!(
+            Checked::new(250u8).wrapping_add(Checked::new(10)),
+            Checked::new(4)
+        );
+        assert!(Checked::new(1u8)
+            .wrapping_div(Checked::new(0))
+            .is_overflowed());
+        assert_eq!(
+            Checked::new(250u8).overflowing_add(Checked::new(10)),
+            (Checked::new(4), true)
+        );
+        assert_eq!(Checked::new(250u8).checked_add(Checked::new(10)), None);
+        assert_eq!(
+            Checked::new(250u8).checked_add(Checked::new(5)),
+            Some(Checked::new(255))
+        );
+        assert!(Checked::new(-1) < Checked::new(1));
+        let overflowed = Checked::new(200u8) + 100;
+        assert!(overflowed.max(Checked::new(1)).is_overflowed());
+        assert!(Checked::new(1).min(overflowed).is_overflowed());
+        assert!(overflowed
+            .clamp(Checked::new(0), Checked::new(10))
+            .is_overflowed());
+        assert!(Checked::new(5)
+            .clamp(Checked::new(0), overflowed)
+            .is_overflowed());
+        assert_eq!(Checked::new(3u8).max(Checked::new(7)), Checked::new(7));
+        assert_eq!(Checked::new(3u8).min(Checked::new(7)), Checked::new(3));
+        assert_eq!(
+            Checked::new(30u8).clamp(Checked::new(0), Checked::new(10)),
+            Checked::new(10)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to use an overflowed value")]
+    fn checked_count_ones_overflowed() {
+        let _ = PrimInt::count_ones(Checked::new(u8::MAX) + 1);
+    }
+
+    #[test]
+    fn strict_test() {
+        let mut x = Strict(100u8);
+        x += 155;
+        assert_eq!(x, Strict(255));
+        x -= Strict(5);
+        x /= 10;
+        x %= Strict(7);
+        x *= 3;
+        assert_eq!(x, Strict(12));
+        assert_eq!(Strict(2u32).pow(31), Strict(1 << 31));
+        assert_eq!(Strict(-7i32).to_string(), "-7");
+    }
+
+    #[test]
+    fn strict_prim_int_test() {
+        assert_eq!(generic_sum(&[Strict(100u8), Strict(100)]), Strict(200));
+        assert_eq!(popcount_sum(&[Strict(7u32), Strict(8)]), 4);
+        assert_eq!(<Strict<i16> as PrimInt>::BITS, 16);
+        assert_eq!(PrimInt::isqrt(Strict(99u64)), Strict(9));
+        assert_eq!(Integer::sqrt(Strict(99u64)), Strict(9));
+        assert_eq!(PrimInt::ilog(Strict(1000u32), Strict(10)), 3);
+        let mut x = Strict(0b1111u8);
+        x &= Strict(0b0110);
+        x |= Strict(0b1000);
+        x ^= Strict(0b0001);
+        x >>= 1;
+        assert_eq!(x, Strict(0b0111));
+        x <<= 5;
+        assert_eq!(x, Strict(0b1110_0000));
+        assert_eq!(Arithmetic::pow(Strict(3u8), 5), Strict(243));
+        assert_eq!(Strict(-7i32).div_euclid(Strict(2)), Strict(-4));
+        assert_eq!(Strict(-7i32).rem_euclid(Strict(2)), Strict(1));
+        assert_eq!(Signed::abs(Strict(-5i8)), Strict(5));
+        assert_eq!(Signed::signum(Strict(-5i8)), Strict(-1));
+        assert_eq!(Strict::<i8>::min_value(), Strict(-128));
+        assert_eq!(Strict(250u8).saturating_add(Strict(10)), Strict(255));
+        assert_eq!(Strict(250u8).wrapping_add(Strict(10)), Strict(4));
+        assert_eq!(Strict(250u8).checked_add(Strict(10)), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to shift left with overflow")]
+    fn strict_shl_overflow() {
+        let _ = Strict(1u32) << 32;
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to shift right with overflow")]
+    fn strict_shr_overflow() {
+        let mut x = Strict(1i8);
+        x >>= 8;
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to subtract with overflow")]
+    fn strict_abs_overflow() {
+        let _ = Signed::abs(Strict(i64::MIN));
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to exponentiate with overflow")]
+    fn strict_arithmetic_pow_overflow() {
+        let _ = Arithmetic::pow(Strict(2u8), 8);
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to add with overflow")]
+    fn strict_add_overflow() {
+        let _ = Strict(u8::MAX) + 1;
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to subtract with overflow")]
+    fn strict_sub_overflow() {
+        let mut x = Strict(0u64);
+        x -= 1;
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to multiply with overflow")]
+    fn strict_mul_overflow() {
+        let _ = Strict(i64::MAX) * Strict(2);
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to divide by zero or with overflow")]
+    fn strict_div_overflow() {
+        let _ = Strict(i32::MIN) / -1;
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to exponentiate with overflow")]
+    fn strict_pow_overflow() {
+        let _ = Strict(10u32).pow(10);
+    }
+
+    #[test]
+    fn floor_ceil_div_test() {
+        for a in i8::MIN..=i8::MAX {
+            for b in i8::MIN..=i8::MAX {
+                if b == 0 || (a == i8::MIN && b == -1) {
+                    continue;
+                }
+                let q = a as f64 / b as f64;
+                assert_eq!(a.floor_div(b) as f64, q.floor(), "{a} {b}");
+                assert_eq!(a.ceil_div(b) as f64, q.ceil(), "{a} {b}");
+            }
+        }
+        for a in 0..=u8::MAX {
+            for b in 1..=u8::MAX {
+                assert_eq!(a.floor_div(b), a / b);
+                assert_eq!(a.ceil_div(b), a.div_ceil(b));
+            }
+        }
+        assert_eq!(i64::MIN.floor_div(1), i64::MIN);
+        assert_eq!(i64::MAX.ceil_div(2), i64::MAX / 2 + 1);
+        assert_eq!(u64::MAX.ceil_div(u64::MAX), 1);
+        assert_eq!(Wrapping(-7i32).floor_div(Wrapping(2)), Wrapping(-4));
+    }
+
+    fn root_naive(n: i64, k: u32) -> i64 {
+        let m = n.abs();
+        let mut r = (m as f64).powf(1.0 / k as f64).round() as i64;
+        while r.pow(k) > m {
+            r -= 1;
+        }
+        while (r + 1).pow(k) <= m {
+            r += 1;
+        }
+        if n < 0 {
+            -r
+        } else {
+            r
+        }
+    }
+
+    #[test]
+    fn nth_root_test() {
+        for n in i16::MIN..=i16::MAX {
+            for k in 1..=7 {
+                if n < 0 && k % 2 == 0 {
+                    continue;
+                }
+                assert_eq!(n.nth_root(k) as i64, root_naive(n as i64, k), "{n} {k}");
+            }
+        }
+        for n in 0..=u16::MAX {
+            assert_eq!(Integer::sqrt(n) as i64, root_naive(n as i64, 2));
+            assert_eq!(n.cbrt() as i64, root_naive(n as i64, 3));
+        }
+        assert_eq!(i8::MIN.cbrt(), -5);
+        assert_eq!(i8::MIN.nth_root(7), -2);
+        assert_eq!(i8::MAX.nth_root(7), 1);
+        assert_eq!(Integer::sqrt(u64::MAX), u32::MAX as u64);
+        assert_eq!(Integer::sqrt(u64::MAX - 1), u32::MAX as u64);
+        assert_eq!(Integer::sqrt((u32::MAX as u64).pow(2)), u32::MAX as u64);
+        assert_eq!(
+            Integer::sqrt((u32::MAX as u64).pow(2) - 1),
+            u32::MAX as u64 - 1
+        );
+        assert_eq!(u64::MAX.cbrt(), 2642245);
+        assert_eq!(i64::MIN.cbrt(), -2097152);
+        assert_eq!(i64::MIN.nth_root(63), -2);
+        assert_eq!((i64::MIN + 1).nth_root(63), -1);
+        assert_eq!(i64::MAX.nth_root(62), 2);
+        assert_eq!(i64::MAX.nth_root(63), 1);
+        assert_eq!(u128::MAX.nth_root(4), u32::MAX as u128);
+        assert_eq!(u128::MAX.nth_root(1000), 1);
+        assert_eq!(Integer::sqrt(0u32), 0);
+        assert_eq!(Integer::sqrt(Saturating(99u64)), Saturating(9));
+        let big = crate::BigUint::from(10u32).pow(100) - crate::BigUint::one();
+        assert_eq!(big.cbrt().to_string(), "2154434690031883721759293566519350");
+    }
+
+    #[test]
+    #[should_panic]
+    fn even_root_of_negative() {
+        (-4i32).sqrt();
+    }
+
+    #[test]
+    fn exists_bigger_int_test() {
+        assert_eq!(usize::MAX.cast_to_twice(), usize::MAX as u128);
+        assert_eq!(isize::cast_from_twice(-3), -3);
+        assert_eq!(u64::MAX.cast_to_twice() * 2, u64::MAX as u128 * 2);
+    }
 }
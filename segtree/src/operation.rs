@@ -15,19 +15,19 @@ pub trait Operator {
 /// An operator is idempotent if `op(a, a) = a` for all `a`.
 pub trait Idempotent: Operator {}
 
-trait HasZero {
+pub trait HasZero {
     const ZERO: Self;
 }
 
-trait HasOne {
+pub trait HasOne {
     const ONE: Self;
 }
 
-trait HasMax {
+pub trait HasMax {
     const MAX: Self;
 }
 
-trait HasMin {
+pub trait HasMin {
     const MIN: Self;
 }
 
@@ -0,0 +1,194 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+const RANK_SHIFT: u32 = 56;
+const PARENT_MASK: u64 = (1 << RANK_SHIFT) - 1;
+
+const fn pack(parent: usize, rank: u64) -> u64 {
+    (rank << RANK_SHIFT) | parent as u64
+}
+
+const fn parent_of(word: u64) -> usize {
+    (word & PARENT_MASK) as usize
+}
+
+const fn rank_of(word: u64) -> u64 {
+    word >> RANK_SHIFT
+}
+
+/// Lock-free union-find that can be shared between threads.
+///
+/// Each node stores its parent and rank packed into a single `AtomicU64`, so
+/// linking two roots is a single CAS. A root is always linked below a root
+/// with a larger `(rank, index)` pair, so the forest never contains cycles
+/// even when several threads race on the same nodes.
+pub struct ConcurrentUnionFind {
+    uf: Box<[AtomicU64]>,
+}
+
+impl ConcurrentUnionFind {
+    pub fn new(size: usize) -> Self {
+        assert!(
+            size as u64 <= PARENT_MASK,
+            "size must be less than or equal to 2^{RANK_SHIFT} - 1"
+        );
+        Self {
+            uf: (0..size).map(|i| AtomicU64::new(pack(i, 0))).collect(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.uf.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Path halving: every visited node is redirected to its grandparent.
+    /// A failed CAS only means another thread already moved the node closer
+    /// to the root, so it is ignored.
+    pub fn find(&self, mut i: usize) -> usize {
+        loop {
+            let word = self.uf[i].load(Ordering::Acquire);
+            let p = parent_of(word);
+            if p == i {
+                return i;
+            }
+            let p_word = self.uf[p].load(Ordering::Acquire);
+            let gp = parent_of(p_word);
+            if p != gp {
+                let _ = self.uf[i].compare_exchange_weak(
+                    word,
+                    pack(gp, rank_of(word)),
+                    Ordering::AcqRel,
+                    Ordering::Relaxed,
+                );
+            }
+            i = gp;
+        }
+    }
+
+    pub fn unite(&self, mut i: usize, mut j: usize) -> bool {
+        loop {
+            i = self.find(i);
+            j = self.find(j);
+            if i == j {
+                return false;
+            }
+            let word_i = self.uf[i].load(Ordering::Acquire);
+            let word_j = self.uf[j].load(Ordering::Acquire);
+            if parent_of(word_i) != i || parent_of(word_j) != j {
+                continue;
+            }
+            let (rank_i, rank_j) = (rank_of(word_i), rank_of(word_j));
+            let (child, child_word, root, root_word) = if (rank_i, i) < (rank_j, j) {
+                (i, word_i, j, word_j)
+            } else {
+                (j, word_j, i, word_i)
+            };
+            if self.uf[child]
+                .compare_exchange(
+                    child_word,
+                    pack(root, rank_of(child_word)),
+                    Ordering::AcqRel,
+                    Ordering::Relaxed,
+                )
+                .is_err()
+            {
+                continue;
+            }
+            if rank_of(child_word) == rank_of(root_word) {
+                let _ = self.uf[root].compare_exchange(
+                    root_word,
+                    pack(root, rank_of(root_word) + 1),
+                    Ordering::AcqRel,
+                    Ordering::Relaxed,
+                );
+            }
+            return true;
+        }
+    }
+
+    /// Returns `true` if `i` and `j` belonged to the same set at some point
+    /// during the call.
+    pub fn same(&self, mut i: usize, mut j: usize) -> bool {
+        loop {
+            i = self.find(i);
+            j = self.find(j);
+            if i == j {
+                return true;
+            }
+            if parent_of(self.uf[i].load(Ordering::Acquire)) == i {
+                return false;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::UnionFind;
+    use rand::Rng;
+    use std::{
+        sync::atomic::{AtomicUsize, Ordering},
+        thread,
+    };
+
+    #[test]
+    fn unite_test() {
+        let uf = ConcurrentUnionFind::new(10);
+        assert!(uf.unite(0, 1));
+        assert!(uf.unite(2, 3));
+        assert!(uf.unite(3, 4));
+        assert!(!uf.unite(2, 4));
+        assert!(uf.same(0, 1));
+        assert!(uf.same(2, 4));
+        assert!(!uf.same(1, 2));
+        assert!(!uf.same(5, 6));
+        assert_eq!(uf.find(2), uf.find(4));
+    }
+
+    #[test]
+    fn stress_test() {
+        const N: usize = 10000;
+        const M: usize = 20000;
+        const THREADS: usize = 8;
+        let mut rng = rand::thread_rng();
+        let edges = (0..M)
+            .map(|_| (rng.gen_range(0..N), rng.gen_range(0..N)))
+            .collect::<Vec<_>>();
+
+        let mut expected = UnionFind::new(vec![(); N]);
+        let mut components = N;
+        for &(u, v) in &edges {
+            if expected.unite(u, v) {
+                components -= 1;
+            }
+        }
+
+        let uf = ConcurrentUnionFind::new(N);
+        let merged = AtomicUsize::new(0);
+        thread::scope(|s| {
+            for chunk in edges.chunks(M.div_ceil(THREADS)) {
+                let uf = &uf;
+                let merged = &merged;
+                s.spawn(move || {
+                    for &(u, v) in chunk {
+                        if uf.unite(u, v) {
+                            merged.fetch_add(1, Ordering::Relaxed);
+                        }
+                        assert!(uf.same(u, v));
+                    }
+                });
+            }
+        });
+
+        assert_eq!(N - merged.load(Ordering::Relaxed), components);
+        for i in 0..N {
+            assert!(uf.same(i, expected.find(i)));
+        }
+        let roots = (0..N).filter(|&i| uf.find(i) == i).count();
+        assert_eq!(roots, components);
+    }
+}
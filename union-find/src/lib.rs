@@ -1,3 +1,8 @@
+pub mod concurrent;
+pub mod parity;
+pub use concurrent::ConcurrentUnionFind;
+pub use parity::{Contradiction, ParityUnionFind};
+
 use std::ops::{Add, Sub};
 
 pub struct UnionFind<T> {
@@ -0,0 +1,170 @@
+/// Returned when a constraint contradicts the parities already recorded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Contradiction;
+
+/// Union-find that additionally tracks whether two elements of the same set
+/// have the same parity (e.g. the same color in a 2-coloring).
+pub struct ParityUnionFind {
+    uf: Vec<usize>,
+    size: Vec<usize>,
+    /// parity relative to the parent
+    parity: Vec<bool>,
+    /// valid only for roots
+    bipartite: Vec<bool>,
+}
+
+impl ParityUnionFind {
+    pub fn new(size: usize) -> Self {
+        Self {
+            uf: (0..size).collect(),
+            size: vec![1; size],
+            parity: vec![false; size],
+            bipartite: vec![true; size],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.uf.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the root of `i` and the parity of `i` relative to the root.
+    pub fn find_parity(&self, mut i: usize) -> (usize, bool) {
+        let mut parity = false;
+        let mut p = self.uf[i];
+        while p != i {
+            parity ^= self.parity[i];
+            i = p;
+            p = self.uf[i];
+        }
+        (p, parity)
+    }
+
+    pub fn find_parity_rc(&mut self, i: usize) -> (usize, bool) {
+        let (root, parity) = self.find_parity(i);
+        let mut to_root = parity;
+        let mut i = i;
+        while i != root {
+            let p = self.uf[i];
+            let to_parent = self.parity[i];
+            self.uf[i] = root;
+            self.parity[i] = to_root;
+            to_root ^= to_parent;
+            i = p;
+        }
+        (root, parity)
+    }
+
+    pub fn find(&self, i: usize) -> usize {
+        self.find_parity(i).0
+    }
+
+    pub fn find_rc(&mut self, i: usize) -> usize {
+        self.find_parity_rc(i).0
+    }
+
+    /// Records that the parities of `i` and `j` differ iff `diff` is `true`.
+    ///
+    /// Returns `Ok(true)` if two sets were merged and `Ok(false)` if the
+    /// constraint was already implied. On a contradiction, the set is marked
+    /// as not bipartite and `Err(Contradiction)` is returned.
+    pub fn unite(&mut self, i: usize, j: usize, diff: bool) -> Result<bool, Contradiction> {
+        let (root_i, parity_i) = self.find_parity_rc(i);
+        let (root_j, parity_j) = self.find_parity_rc(j);
+        let parity = parity_i ^ parity_j ^ diff;
+        if root_i != root_j {
+            let size_i = self.size[root_i];
+            let size_j = self.size[root_j];
+            let bipartite = self.bipartite[root_i] && self.bipartite[root_j];
+            if size_i > size_j {
+                self.uf[root_j] = root_i;
+                self.parity[root_j] = parity;
+                self.size[root_i] = size_i + size_j;
+                self.bipartite[root_i] = bipartite;
+            } else {
+                self.uf[root_i] = root_j;
+                self.parity[root_i] = parity;
+                self.size[root_j] = size_i + size_j;
+                self.bipartite[root_j] = bipartite;
+            }
+            Ok(true)
+        } else if parity {
+            self.bipartite[root_i] = false;
+            Err(Contradiction)
+        } else {
+            Ok(false)
+        }
+    }
+
+    /// Records that `i` and `j` have the same parity.
+    pub fn unite_same(&mut self, i: usize, j: usize) -> Result<bool, Contradiction> {
+        self.unite(i, j, false)
+    }
+
+    /// Records that `i` and `j` have different parities.
+    pub fn unite_diff(&mut self, i: usize, j: usize) -> Result<bool, Contradiction> {
+        self.unite(i, j, true)
+    }
+
+    /// Returns whether the parities of `i` and `j` differ, or `None` if they
+    /// belong to different sets.
+    pub fn diff(&self, i: usize, j: usize) -> Option<bool> {
+        let (root_i, parity_i) = self.find_parity(i);
+        let (root_j, parity_j) = self.find_parity(j);
+        (root_i == root_j).then_some(parity_i ^ parity_j)
+    }
+
+    /// Returns `true` if no constraint in the set of `i` has been contradicted.
+    pub fn is_bipartite(&self, i: usize) -> bool {
+        self.bipartite[self.find(i)]
+    }
+
+    pub fn size(&self, i: usize) -> usize {
+        self.size[self.find(i)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unite_test() {
+        let mut uf = ParityUnionFind::new(6);
+        assert_eq!(uf.unite_diff(0, 1), Ok(true));
+        assert_eq!(uf.unite_diff(1, 2), Ok(true));
+        assert_eq!(uf.unite_same(3, 4), Ok(true));
+        assert_eq!(uf.unite_same(0, 2), Ok(false));
+        assert_eq!(uf.diff(0, 2), Some(false));
+        assert_eq!(uf.diff(0, 1), Some(true));
+        assert_eq!(uf.diff(0, 3), None);
+        assert_eq!(uf.unite_diff(2, 3), Ok(true));
+        assert_eq!(uf.diff(0, 4), Some(true));
+        assert_eq!(uf.size(4), 5);
+        assert!(uf.is_bipartite(0));
+
+        assert_eq!(uf.unite_diff(0, 2), Err(Contradiction));
+        assert!(!uf.is_bipartite(4));
+        assert!(uf.is_bipartite(5));
+        assert_eq!(uf.unite_same(5, 0), Ok(true));
+        assert!(!uf.is_bipartite(5));
+    }
+
+    #[test]
+    fn odd_cycle_test() {
+        let n = 7;
+        let mut uf = ParityUnionFind::new(n);
+        for i in 0..n - 1 {
+            assert_eq!(uf.unite_diff(i, i + 1), Ok(true));
+        }
+        for i in 0..n {
+            assert_eq!(uf.find_parity_rc(i).1, uf.find_parity_rc(0).1 ^ (i % 2 == 1));
+        }
+        assert!(uf.is_bipartite(3));
+        assert_eq!(uf.unite_diff(n - 1, 0), Err(Contradiction));
+        assert!(!uf.is_bipartite(3));
+    }
+}
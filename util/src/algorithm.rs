@@ -1,60 +1,32 @@
-use std::{
-    iter::FusedIterator,
-    ops::{Add, Div, Mul, RangeBounds, Rem, Sub},
-};
-
-pub trait Integer:
-    Sized
-    + Add<Output = Self>
-    + Sub<Output = Self>
-    + Mul<Output = Self>
-    + Div<Output = Self>
-    + Rem<Output = Self>
-    + Ord
-    + Copy
-{
-    const MIN: Self;
-    const MAX: Self;
-    const ZERO: Self;
-    const ONE: Self;
-    const TWO: Self;
-}
-
-macro_rules! impl_integer {
-    ($($t:ty),*) => {
-        $(
-            impl Integer for $t {
-                const MIN: Self = <$t>::MIN;
-                const MAX: Self = <$t>::MAX;
-                const ZERO: Self = 0;
-                const ONE: Self = 1;
-                const TWO: Self = 2;
-            }
-        )*
-    };
-}
+use std::{iter::FusedIterator, ops::RangeBounds};
+
+use number::traits::PrimInt;
+use query::{Group, Monoid};
 
-impl_integer!(i8, i16, i32, i64, i128, isize);
-impl_integer!(u8, u16, u32, u64, u128, usize);
+use crate::{cumulative::CumulativeFold, sliding_window::SlidingWindowFold};
 
 /// `range`が`l..r`で、返り値を`i`とすると、
 /// `(l..i).contains(j)`となる`j`において、`f(j)`が`true`となり、
 /// `(i..r).contains(j)`となる`j`において、`f(j)`が`false`となる。
-pub fn upper_bound<T: Integer>(range: impl RangeBounds<T>, mut f: impl FnMut(T) -> bool) -> T {
+///
+/// `range`の端が`Unbounded`の場合は`T::min_value() / 2`、`T::max_value() / 2`とみなす。
+/// 上限の分からない範囲を探索する場合は[`crate::search::exponential_search`]を使う。
+pub fn upper_bound<T: PrimInt>(range: impl RangeBounds<T>, mut f: impl FnMut(T) -> bool) -> T {
+    let two = T::one() + T::one();
     let mut l = match range.start_bound() {
         std::ops::Bound::Included(&l) => l,
-        std::ops::Bound::Excluded(&l) => l + T::ONE,
-        std::ops::Bound::Unbounded => T::MIN / T::TWO,
+        std::ops::Bound::Excluded(&l) => l + T::one(),
+        std::ops::Bound::Unbounded => T::min_value() / two,
     };
     let mut r = match range.end_bound() {
-        std::ops::Bound::Included(&r) => r + T::ONE,
+        std::ops::Bound::Included(&r) => r + T::one(),
         std::ops::Bound::Excluded(&r) => r,
-        std::ops::Bound::Unbounded => T::MAX / T::TWO,
+        std::ops::Bound::Unbounded => T::max_value() / two,
     };
-    while r - l > T::ONE {
-        let m = l + (r - l) / T::TWO;
+    while r - l > T::one() {
+        let m = l + (r - l) / two;
         if f(m) {
-            l = m + T::ONE;
+            l = m + T::one();
         } else {
             r = m;
         }
@@ -62,7 +34,7 @@ pub fn upper_bound<T: Integer>(range: impl RangeBounds<T>, mut f: impl FnMut(T)
     if l == r {
         l
     } else if f(l) {
-        l + T::ONE
+        l + T::one()
     } else {
         l
     }
@@ -71,10 +43,427 @@ pub fn upper_bound<T: Integer>(range: impl RangeBounds<T>, mut f: impl FnMut(T)
 /// `range`が`l..r`で、返り値を`i`とすると、
 /// `(l..i).contains(j)`となる`j`において、`f(j)`が`false`となり、
 /// `(i..r).contains(j)`となる`j`において、`f(j)`が`true`となる。
-pub fn lower_bound<T: Integer>(range: impl RangeBounds<T>, mut f: impl FnMut(T) -> bool) -> T {
+pub fn lower_bound<T: PrimInt>(range: impl RangeBounds<T>, mut f: impl FnMut(T) -> bool) -> T {
     upper_bound(range, |x| !f(x))
 }
 
+/// 並列二分探索を行う。
+///
+/// `state`に`apply(state, 0)`, `apply(state, 1)`, ..., `apply(state, steps - 1)`を順に適用していくとき、
+/// 各クエリ`q`について、最初の`t`個の操作を適用した状態で`pred(state, q)`が`true`となる最小の`t`を求める。
+/// `pred`は`t`について単調(一度`true`になったら`true`のまま)である必要がある。
+/// すべての操作を適用しても`false`の場合は`None`となる。
+///
+/// 各ラウンドの最初に`reset(state)`で操作を1つも適用していない状態に戻す。
+/// ラウンド数はO(log steps)で、全体の計算量はO((Q + steps) log steps)回の`apply`と`pred`の呼び出し。
+pub fn parallel_binary_search<S, Q>(
+    state: &mut S,
+    steps: usize,
+    queries: &[Q],
+    mut reset: impl FnMut(&mut S),
+    mut apply: impl FnMut(&mut S, usize),
+    mut pred: impl FnMut(&mut S, &Q) -> bool,
+) -> Vec<Option<usize>> {
+    const NIL: usize = usize::MAX;
+    // 答えは`lo..=hi`の中にあり、`hi == steps + 1`は解なしを表す
+    let mut lo = vec![0; queries.len()];
+    let mut hi = vec![steps + 1; queries.len()];
+    let mut head = vec![NIL; steps + 1];
+    let mut next = vec![NIL; queries.len()];
+    loop {
+        let mut last = None;
+        for (i, (&l, &h)) in lo.iter().zip(&hi).enumerate().rev() {
+            if l < h {
+                let m = l + (h - l) / 2;
+                next[i] = head[m];
+                head[m] = i;
+                last = last.max(Some(m));
+            }
+        }
+        let Some(last) = last else {
+            break;
+        };
+        reset(state);
+        for (t, head) in head.iter_mut().enumerate().take(last + 1) {
+            let mut i = std::mem::replace(head, NIL);
+            while i != NIL {
+                if pred(state, &queries[i]) {
+                    hi[i] = t;
+                } else {
+                    lo[i] = t + 1;
+                }
+                i = next[i];
+            }
+            if t < last {
+                apply(state, t);
+            }
+        }
+    }
+    lo.into_iter().map(|t| (t <= steps).then_some(t)).collect()
+}
+
+/// 座標圧縮
+///
+/// 値を昇順に`0, 1, 2, ...`の添字に対応させる。
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Compressor<T> {
+    values: Vec<T>,
+}
+
+impl<T: Ord> Compressor<T> {
+    pub fn new<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut values = iter.into_iter().collect::<Vec<_>>();
+        values.sort_unstable();
+        values.dedup();
+        Self { values }
+    }
+
+    /// 異なる値の個数
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// `x`に対応する添字を返す。`x`が含まれていない場合は`None`。
+    pub fn index(&self, x: &T) -> Option<usize> {
+        self.values.binary_search(x).ok()
+    }
+
+    /// `x`以上の最小の値の添字を返す。そのような値がない場合は`self.len()`。
+    pub fn lower_bound(&self, x: &T) -> usize {
+        self.values.partition_point(|v| v < x)
+    }
+
+    /// `x`より大きい最小の値の添字を返す。そのような値がない場合は`self.len()`。
+    pub fn upper_bound(&self, x: &T) -> usize {
+        self.values.partition_point(|v| v <= x)
+    }
+
+    /// 添字`i`に対応する値を返す。
+    pub fn get(&self, i: usize) -> Option<&T> {
+        self.values.get(i)
+    }
+
+    /// `iter`の各値を添字に変換する。含まれていない値があるとpanicする。
+    pub fn compress<'a, I>(&'a self, iter: I) -> Vec<usize>
+    where
+        I: IntoIterator<Item = &'a T>,
+    {
+        iter.into_iter()
+            .map(|x| self.index(x).expect("value is not in the compressor."))
+            .collect()
+    }
+
+    /// 昇順に並んだ値
+    pub fn as_slice(&self) -> &[T] {
+        &self.values
+    }
+
+    pub fn into_vec(self) -> Vec<T> {
+        self.values
+    }
+}
+
+impl<T: Ord> FromIterator<T> for Compressor<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::new(iter)
+    }
+}
+
+impl<T> std::ops::Index<usize> for Compressor<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        &self.values[index]
+    }
+}
+
+/// `a[i] > a[j]`となる`i < j`の組の個数をマージソートでO(n log n)で求める。
+pub fn inversion_count<T: Ord>(v: Vec<T>) -> usize {
+    fn sort<T: Ord>(mut v: Vec<T>) -> (Vec<T>, usize) {
+        if v.len() <= 1 {
+            return (v, 0);
+        }
+        let right = v.split_off(v.len() / 2);
+        let (left, mut count) = sort(v);
+        let (right, c) = sort(right);
+        count += c;
+        let mut merged = Vec::with_capacity(left.len() + right.len());
+        let mut rest = left.len();
+        let mut left = left.into_iter().peekable();
+        let mut right = right.into_iter().peekable();
+        while let (Some(l), Some(r)) = (left.peek(), right.peek()) {
+            if r < l {
+                merged.push(right.next().unwrap());
+                count += rest;
+            } else {
+                merged.push(left.next().unwrap());
+                rest -= 1;
+            }
+        }
+        merged.extend(left);
+        merged.extend(right);
+        (merged, count)
+    }
+    sort(v).1
+}
+
+/// `v`を辞書順で次の順列に並べ替える。
+///
+/// `v`が辞書順で最後の順列の場合は、最初の順列(昇順)に並べ替えて`false`を返す。
+pub fn next_permutation<T: Ord>(v: &mut [T]) -> bool {
+    let Some(i) = v.windows(2).rposition(|w| w[0] < w[1]) else {
+        v.reverse();
+        return false;
+    };
+    let j = v.iter().rposition(|x| v[i] < *x).unwrap();
+    v.swap(i, j);
+    v[i + 1..].reverse();
+    true
+}
+
+/// `v`を辞書順で前の順列に並べ替える。
+///
+/// `v`が辞書順で最初の順列の場合は、最後の順列(降順)に並べ替えて`false`を返す。
+pub fn prev_permutation<T: Ord>(v: &mut [T]) -> bool {
+    let Some(i) = v.windows(2).rposition(|w| w[0] > w[1]) else {
+        v.reverse();
+        return false;
+    };
+    let j = v.iter().rposition(|x| v[i] > *x).unwrap();
+    v.swap(i, j);
+    v[i + 1..].reverse();
+    true
+}
+
+/// `arr`から始めて、辞書順で後ろの順列を順に返す。
+///
+/// すべての順列を列挙するには`arr`を昇順に並べておく。等しい要素があっても同じ順列は一度しか現れない。
+pub fn permutations<T: Ord + Clone, const N: usize>(arr: [T; N]) -> Permutations<T, N> {
+    Permutations { arr, done: false }
+}
+
+pub struct Permutations<T, const N: usize> {
+    arr: [T; N],
+    done: bool,
+}
+
+impl<T: Ord + Clone, const N: usize> Iterator for Permutations<T, N> {
+    type Item = [T; N];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let current = self.arr.clone();
+        self.done = !next_permutation(&mut self.arr);
+        Some(current)
+    }
+}
+
+impl<T: Ord + Clone, const N: usize> FusedIterator for Permutations<T, N> {}
+
+/// `{0, 1, ..., n - 1}`から`k`個選ぶ組合せを、ビットマスクとして昇順に返す。`n <= 64`である必要がある。
+pub fn combinations(n: u32, k: u32) -> Combinations {
+    assert!(n <= 64, "n must be at most 64.");
+    let remaining = if k > n {
+        0
+    } else {
+        (0..k as u128).fold(1, |c, i| c * (n as u128 - i) / (i + 1)) as u64
+    };
+    Combinations {
+        current: if k > n { 0 } else { (1u128 << k) - 1 },
+        remaining,
+    }
+}
+
+pub struct Combinations {
+    current: u128,
+    remaining: u64,
+}
+
+impl Iterator for Combinations {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let x = self.current;
+        if self.remaining > 0 {
+            // 立っているビットの数を保ったまま次に大きい数にする
+            let c = x & x.wrapping_neg();
+            let r = x + c;
+            self.current = (((r ^ x) >> 2) / c) | r;
+        }
+        Some(x as u64)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match usize::try_from(self.remaining) {
+            Ok(n) => (n, Some(n)),
+            Err(_) => (usize::MAX, None),
+        }
+    }
+}
+
+impl FusedIterator for Combinations {}
+
+/// `mask`の部分集合を、ビットマスクとして降順に返す。`mask`自身と`0`も含む。
+pub fn subsets(mask: u64) -> Subsets {
+    Subsets {
+        mask,
+        current: mask,
+        remaining: 1 << mask.count_ones(),
+    }
+}
+
+pub struct Subsets {
+    mask: u64,
+    current: u64,
+    remaining: u128,
+}
+
+impl Iterator for Subsets {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let x = self.current;
+        self.current = x.wrapping_sub(1) & self.mask;
+        Some(x)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match usize::try_from(self.remaining) {
+            Ok(n) => (n, Some(n)),
+            Err(_) => (usize::MAX, None),
+        }
+    }
+}
+
+impl FusedIterator for Subsets {}
+
+/// `0..1 << n`をグレイコードの順に返す。`n <= 64`である必要がある。
+///
+/// `i`番目と`i + 1`番目の値は、`(i + 1).trailing_zeros()`番目のビットだけが異なる。
+pub fn gray_code(n: u32) -> GrayCode {
+    assert!(n <= 64, "n must be at most 64.");
+    GrayCode { i: 0, end: 1 << n }
+}
+
+pub struct GrayCode {
+    i: u128,
+    end: u128,
+}
+
+impl Iterator for GrayCode {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.i == self.end {
+            return None;
+        }
+        let code = self.i ^ (self.i >> 1);
+        self.i += 1;
+        Some(code as u64)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match usize::try_from(self.end - self.i) {
+            Ok(n) => (n, Some(n)),
+            Err(_) => (usize::MAX, None),
+        }
+    }
+}
+
+impl FusedIterator for GrayCode {}
+
+/// `mask`の立っているビットの位置を昇順に返す。
+pub fn bits(mask: u64) -> Bits {
+    Bits { mask }
+}
+
+pub struct Bits {
+    mask: u64,
+}
+
+impl Iterator for Bits {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.mask == 0 {
+            return None;
+        }
+        let i = self.mask.trailing_zeros();
+        self.mask &= self.mask - 1;
+        Some(i)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.mask.count_ones() as usize;
+        (n, Some(n))
+    }
+}
+
+impl ExactSizeIterator for Bits {}
+
+impl FusedIterator for Bits {}
+
+/// `0..dims[0]`, `0..dims[1]`, ...の直積を辞書順に返す。
+pub fn cartesian_product<const N: usize>(dims: [usize; N]) -> CartesianProduct<N> {
+    let remaining = dims
+        .iter()
+        .try_fold(1usize, |acc, &d| acc.checked_mul(d))
+        .expect("too many elements in cartesian product.");
+    CartesianProduct {
+        dims,
+        current: [0; N],
+        remaining,
+    }
+}
+
+pub struct CartesianProduct<const N: usize> {
+    dims: [usize; N],
+    current: [usize; N],
+    remaining: usize,
+}
+
+impl<const N: usize> Iterator for CartesianProduct<N> {
+    type Item = [usize; N];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let current = self.current;
+        for (x, &d) in self.current.iter_mut().zip(&self.dims).rev() {
+            *x += 1;
+            if *x < d {
+                break;
+            }
+            *x = 0;
+        }
+        Some(current)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<const N: usize> ExactSizeIterator for CartesianProduct<N> {}
+
+impl<const N: usize> FusedIterator for CartesianProduct<N> {}
+
 pub trait IteratorExt: Iterator {
     /// 累積和を求める
     fn cumulative_sum<T, F>(self, init: T, f: F) -> CumSum<Self, T, F>
@@ -88,6 +477,71 @@ pub trait IteratorExt: Iterator {
             f,
         }
     }
+
+    /// 群の累積和を求め、逆元を使って任意の区間の積をO(1)で求められるようにする。
+    fn cumulative_fold<G>(self, group: G) -> CumulativeFold<G>
+    where
+        Self: Sized,
+        G: Group<Element = Self::Item>,
+    {
+        CumulativeFold::new(group, self)
+    }
+
+    /// 幅`width`の各区間の積を先頭から順に返す。
+    fn sliding_window_fold<M>(self, monoid: M, width: usize) -> SlidingWindowFold<Self, M>
+    where
+        Self: Sized,
+        M: Monoid<Element = Self::Item>,
+    {
+        SlidingWindowFold::new(self, monoid, width)
+    }
+
+    /// 連続する等しい要素をまとめ、`(要素, 個数)`を返す。
+    fn run_length_encode(self) -> RunLength<Self>
+    where
+        Self: Sized,
+        Self::Item: PartialEq,
+    {
+        RunLength {
+            iter: self,
+            pending: None,
+        }
+    }
+
+    /// `(要素, 個数)`の列を展開する。[`IteratorExt::run_length_encode`]の逆。
+    fn run_length_decode<T>(self) -> RunLengthDecode<Self, T>
+    where
+        Self: Sized + Iterator<Item = (T, usize)>,
+        T: Clone,
+    {
+        RunLengthDecode {
+            iter: self,
+            current: None,
+        }
+    }
+
+    /// `key`が等しい連続する要素をまとめ、`(最初の要素, 個数)`を返す。
+    fn dedup_by_key_count<K, F>(self, key: F) -> DedupByKeyCount<Self, K, F>
+    where
+        Self: Sized,
+        K: PartialEq,
+        F: FnMut(&Self::Item) -> K,
+    {
+        DedupByKeyCount {
+            iter: self,
+            key,
+            pending: None,
+        }
+    }
+
+    /// 転倒数を求める。
+    fn inversion_count(self) -> usize
+    where
+        Self: Sized,
+        Self::Item: Ord,
+    {
+        inversion_count(self.collect())
+    }
 }
 
 impl<I: Iterator> IteratorExt for I {}
@@ -141,9 +595,124 @@ where
 {
 }
 
+pub struct RunLength<I: Iterator> {
+    iter: I,
+    pending: Option<I::Item>,
+}
+
+impl<I> Iterator for RunLength<I>
+where
+    I: Iterator,
+    I::Item: PartialEq,
+{
+    type Item = (I::Item, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let first = self.pending.take().or_else(|| self.iter.next())?;
+        let mut count = 1;
+        for x in self.iter.by_ref() {
+            if x == first {
+                count += 1;
+            } else {
+                self.pending = Some(x);
+                break;
+            }
+        }
+        Some((first, count))
+    }
+}
+
+impl<I> FusedIterator for RunLength<I>
+where
+    I: FusedIterator,
+    I::Item: PartialEq,
+{
+}
+
+pub struct RunLengthDecode<I, T> {
+    iter: I,
+    current: Option<(T, usize)>,
+}
+
+impl<I, T> Iterator for RunLengthDecode<I, T>
+where
+    I: Iterator<Item = (T, usize)>,
+    T: Clone,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match &mut self.current {
+                Some((x, count)) if *count > 1 => {
+                    *count -= 1;
+                    return Some(x.clone());
+                }
+                Some(_) => return self.current.take().map(|(x, _)| x),
+                None => self.current = Some(self.iter.next()?).filter(|&(_, c)| c > 0),
+            }
+        }
+    }
+}
+
+impl<I, T> FusedIterator for RunLengthDecode<I, T>
+where
+    I: FusedIterator<Item = (T, usize)>,
+    T: Clone,
+{
+}
+
+pub struct DedupByKeyCount<I: Iterator, K, F> {
+    iter: I,
+    key: F,
+    pending: Option<(I::Item, K)>,
+}
+
+impl<I, K, F> Iterator for DedupByKeyCount<I, K, F>
+where
+    I: Iterator,
+    K: PartialEq,
+    F: FnMut(&I::Item) -> K,
+{
+    type Item = (I::Item, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (first, key) = match self.pending.take() {
+            Some(pending) => pending,
+            None => {
+                let x = self.iter.next()?;
+                let k = (self.key)(&x);
+                (x, k)
+            }
+        };
+        let mut count = 1;
+        for x in self.iter.by_ref() {
+            let k = (self.key)(&x);
+            if k == key {
+                count += 1;
+            } else {
+                self.pending = Some((x, k));
+                break;
+            }
+        }
+        Some((first, count))
+    }
+}
+
+impl<I, K, F> FusedIterator for DedupByKeyCount<I, K, F>
+where
+    I: FusedIterator,
+    K: PartialEq,
+    F: FnMut(&I::Item) -> K,
+{
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use number::traits::Wrapping;
+    use rand::Rng;
+    use union_find::UnionFind;
 
     #[test]
     fn upper_bound_test() {
@@ -160,6 +729,248 @@ mod tests {
         assert_eq!(lower_bound(0..v.len(), |i| v[i] > 29), v.len());
         let v = [100, 100];
         assert_eq!(upper_bound(0..v.len(), |i| v[i] == 100), v.len());
+        let f = |x: i64| x < 0 || x.checked_mul(x).is_some_and(|y| y <= 1_000_000);
+        assert_eq!(upper_bound(.., f), 1001);
+        let w = upper_bound(Wrapping(0u32)..Wrapping(100), |x| x.0 * x.0 < 50);
+        assert_eq!(w, Wrapping(8));
+    }
+
+    #[test]
+    fn parallel_binary_search_test() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..50 {
+            let n = rng.gen_range(1..30);
+            let edges = (0..rng.gen_range(0..60))
+                .map(|_| (rng.gen_range(0..n), rng.gen_range(0..n)))
+                .collect::<Vec<_>>();
+            // (u, v, k): uとvが連結で、uを含む連結成分の大きさがk以上
+            let queries = (0..rng.gen_range(0..100))
+                .map(|_| {
+                    (
+                        rng.gen_range(0..n),
+                        rng.gen_range(0..n),
+                        rng.gen_range(1..=n),
+                    )
+                })
+                .collect::<Vec<_>>();
+            let pred = |uf: &mut UnionFind<()>, &(u, v, k): &(usize, usize, usize)| {
+                uf.find_rc(u) == uf.find_rc(v) && uf.size_rc(u) >= k
+            };
+            let mut uf = UnionFind::new(vec![(); n]);
+            let mut resets = 0;
+            let ans = parallel_binary_search(
+                &mut uf,
+                edges.len(),
+                &queries,
+                |uf| {
+                    resets += 1;
+                    *uf = UnionFind::new(vec![(); n]);
+                },
+                |uf, t| {
+                    uf.unite(edges[t].0, edges[t].1);
+                },
+                pred,
+            );
+            assert!(resets <= (edges.len() + 2).ilog2() as usize + 1);
+            for (q, ans) in queries.iter().zip(ans) {
+                let mut uf = UnionFind::new(vec![(); n]);
+                let mut expected = None;
+                for t in 0..=edges.len() {
+                    if pred(&mut uf, q) {
+                        expected = Some(t);
+                        break;
+                    }
+                    if t < edges.len() {
+                        uf.unite(edges[t].0, edges[t].1);
+                    }
+                }
+                assert_eq!(ans, expected);
+            }
+        }
+        let ans = parallel_binary_search(
+            &mut 0,
+            10,
+            &[0, 3, 10, 11],
+            |s| *s = 0,
+            |s, _| *s += 1,
+            |s, &q| *s >= q,
+        );
+        assert_eq!(ans, [Some(0), Some(3), Some(10), None]);
+        let ans = parallel_binary_search(&mut (), 0, &[true, false], |_| {}, |_, _| {}, |_, &q| q);
+        assert_eq!(ans, [Some(0), None]);
+    }
+
+    #[test]
+    fn compressor_test() {
+        let v = [30, 10, 50, 10, 20, 50];
+        let comp = v.iter().copied().collect::<Compressor<_>>();
+        assert_eq!(comp.len(), 4);
+        assert_eq!(comp.as_slice(), [10, 20, 30, 50]);
+        assert_eq!(comp.compress(&v), [2, 0, 3, 0, 1, 3]);
+        assert_eq!(comp.index(&20), Some(1));
+        assert_eq!(comp.index(&25), None);
+        assert_eq!(comp.lower_bound(&20), 1);
+        assert_eq!(comp.lower_bound(&25), 2);
+        assert_eq!(comp.lower_bound(&60), 4);
+        assert_eq!(comp.upper_bound(&20), 2);
+        assert_eq!(comp.upper_bound(&5), 0);
+        assert_eq!(comp[3], 50);
+        assert_eq!(comp.get(4), None);
+        assert!(Compressor::<i32>::new([]).is_empty());
+        let comp = Compressor::new(["b", "a", "c", "a"]);
+        assert_eq!(comp.into_vec(), ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn run_length_test() {
+        let s = "aaabccddddx";
+        let rle = s.chars().run_length_encode().collect::<Vec<_>>();
+        assert_eq!(rle, [('a', 3), ('b', 1), ('c', 2), ('d', 4), ('x', 1)]);
+        let decoded = rle.into_iter().run_length_decode().collect::<String>();
+        assert_eq!(decoded, s);
+        assert_eq!("".chars().run_length_encode().count(), 0);
+        let decoded = [(1, 0), (2, 2), (3, 0)]
+            .into_iter()
+            .run_length_decode()
+            .collect::<Vec<_>>();
+        assert_eq!(decoded, [2, 2]);
+
+        let v = [1, 3, 5, 2, 4, 7, 6, 6];
+        let groups = v.iter().dedup_by_key_count(|&&x| x % 2).collect::<Vec<_>>();
+        assert_eq!(groups, [(&1, 3), (&2, 2), (&7, 1), (&6, 2)]);
+    }
+
+    #[test]
+    fn inversion_count_test() {
+        assert_eq!(inversion_count(vec![3, 1, 2]), 2);
+        assert_eq!(inversion_count(Vec::<i32>::new()), 0);
+        assert_eq!([2, 2, 1].into_iter().inversion_count(), 2);
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let n = rng.gen_range(0..100);
+            let v = (0..n).map(|_| rng.gen_range(0..20)).collect::<Vec<_>>();
+            let mut expected = 0;
+            for i in 0..n {
+                for j in i + 1..n {
+                    if v[i] > v[j] {
+                        expected += 1;
+                    }
+                }
+            }
+            assert_eq!(v.iter().inversion_count(), expected);
+        }
+        assert_eq!((0..1000).rev().inversion_count(), 1000 * 999 / 2);
+    }
+
+    #[test]
+    fn permutation_test() {
+        let mut v = [1, 2, 3];
+        let mut all = vec![v];
+        while next_permutation(&mut v) {
+            all.push(v);
+        }
+        assert_eq!(
+            all,
+            [
+                [1, 2, 3],
+                [1, 3, 2],
+                [2, 1, 3],
+                [2, 3, 1],
+                [3, 1, 2],
+                [3, 2, 1]
+            ]
+        );
+        assert_eq!(v, [1, 2, 3]);
+        let mut w = [3, 2, 1];
+        let mut rev = vec![w];
+        while prev_permutation(&mut w) {
+            rev.push(w);
+        }
+        all.reverse();
+        assert_eq!(rev, all);
+        assert_eq!(w, [3, 2, 1]);
+
+        assert_eq!(permutations([0, 1, 2, 3, 4]).count(), 120);
+        let p = permutations([1, 1, 2]).collect::<Vec<_>>();
+        assert_eq!(p, [[1, 1, 2], [1, 2, 1], [2, 1, 1]]);
+        assert_eq!(permutations([2, 1, 3]).count(), 4);
+        assert_eq!(permutations::<i32, 0>([]).count(), 1);
+        let mut empty: [i32; 0] = [];
+        assert!(!next_permutation(&mut empty));
+    }
+
+    #[test]
+    fn combination_test() {
+        for n in 0..=10 {
+            for k in 0..=n + 1 {
+                let c = combinations(n, k);
+                let len = c.size_hint();
+                let masks = c.collect::<Vec<_>>();
+                let expected = (0..1u64 << n)
+                    .filter(|m| m.count_ones() == k)
+                    .collect::<Vec<_>>();
+                assert_eq!(masks, expected);
+                assert_eq!(len, (expected.len(), Some(expected.len())));
+            }
+        }
+        assert_eq!(combinations(10, 200).count(), 0);
+        let mut c = combinations(64, 63);
+        assert_eq!(c.size_hint(), (64, Some(64)));
+        assert_eq!(c.next(), Some(u64::MAX >> 1));
+        assert_eq!(c.last(), Some(u64::MAX - 1));
+        assert_eq!(combinations(64, 64).collect::<Vec<_>>(), [u64::MAX]);
+        assert_eq!(combinations(64, 32).remaining, 1832624140942590534);
+    }
+
+    #[test]
+    fn subsets_test() {
+        let s = subsets(0b1011).collect::<Vec<_>>();
+        assert_eq!(
+            s,
+            [0b1011, 0b1010, 0b1001, 0b1000, 0b0011, 0b0010, 0b0001, 0]
+        );
+        assert_eq!(subsets(0b1011).size_hint(), (8, Some(8)));
+        assert_eq!(subsets(0).collect::<Vec<_>>(), [0]);
+        let mut s = subsets(u64::MAX);
+        assert_eq!(s.size_hint(), (usize::MAX, None));
+        assert_eq!(s.next(), Some(u64::MAX));
+        assert_eq!(s.next(), Some(u64::MAX - 1));
+
+        let b = bits(0b1010_0110);
+        assert_eq!(b.len(), 4);
+        assert_eq!(b.collect::<Vec<_>>(), [1, 2, 5, 7]);
+        assert_eq!(bits(1 << 63).collect::<Vec<_>>(), [63]);
+    }
+
+    #[test]
+    fn gray_code_test() {
+        for n in 0..10 {
+            let codes = gray_code(n).collect::<Vec<_>>();
+            assert_eq!(codes.len(), 1 << n);
+            assert_eq!(gray_code(n).size_hint(), (1 << n, Some(1 << n)));
+            let mut sorted = codes.clone();
+            sorted.sort();
+            assert!(sorted.into_iter().eq(0..1 << n));
+            for (i, w) in codes.windows(2).enumerate() {
+                assert_eq!(w[0] ^ w[1], 1 << (i + 1).trailing_zeros());
+            }
+        }
+        assert_eq!(gray_code(64).nth(3), Some(0b10));
+    }
+
+    #[test]
+    fn cartesian_product_test() {
+        let p = cartesian_product([2, 3]);
+        assert_eq!(p.len(), 6);
+        assert_eq!(
+            p.collect::<Vec<_>>(),
+            [[0, 0], [0, 1], [0, 2], [1, 0], [1, 1], [1, 2]]
+        );
+        assert_eq!(cartesian_product([3, 0, 2]).count(), 0);
+        assert_eq!(cartesian_product([]).collect::<Vec<_>>(), [[]]);
+        let p = cartesian_product([2, 2, 2]).collect::<Vec<_>>();
+        assert_eq!(p.len(), 8);
+        assert_eq!(p[5], [1, 0, 1]);
     }
 
     #[test]
@@ -0,0 +1,515 @@
+use std::ops::{Bound, Range, RangeBounds};
+
+use query::{AbelianGroup, Group};
+
+/// 戻り値を`(l, r)`とすると`l <= r <= len`が保証される。
+fn get_lr<R: RangeBounds<usize>>(range: R, len: usize) -> (usize, usize) {
+    use Bound::*;
+    let l = match range.start_bound() {
+        Excluded(s) => s
+            .checked_add(1)
+            .unwrap_or_else(|| panic!("attempted to index slice from after maximum usize")),
+        Included(s) => *s,
+        Unbounded => 0,
+    };
+    let r = match range.end_bound() {
+        Excluded(e) => *e,
+        Included(e) => e
+            .checked_add(1)
+            .unwrap_or_else(|| panic!("attempted to index slice up to maximum usize")),
+        Unbounded => len,
+    };
+    if l > r {
+        panic!("slice index starts at {l} but ends at {r}");
+    } else if r > len {
+        panic!("range end index {r} out of range for slice of length {len}");
+    }
+    (l, r)
+}
+
+/// 群の累積和。逆元を使って任意の区間の積をO(1)で求める。
+///
+/// 群は可換でなくてもよい。
+#[derive(Debug, Clone)]
+pub struct CumulativeFold<G: Group> {
+    group: G,
+    prefix: Vec<G::Element>,
+}
+
+impl<G: Group> CumulativeFold<G> {
+    pub fn new<I: IntoIterator<Item = G::Element>>(group: G, iter: I) -> Self {
+        let iter = iter.into_iter();
+        let mut prefix = Vec::with_capacity(iter.size_hint().0 + 1);
+        let mut acc = group.identity();
+        prefix.push(acc.clone());
+        for x in iter {
+            group.op_assign(&mut acc, &x);
+            prefix.push(acc.clone());
+        }
+        Self { group, prefix }
+    }
+
+    /// 元の列の長さ
+    pub fn len(&self) -> usize {
+        self.prefix.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 先頭`i`個の積
+    pub fn prefix(&self, i: usize) -> &G::Element {
+        &self.prefix[i]
+    }
+
+    /// `range`に含まれる要素の積を先頭から順に掛けたものを返す。
+    pub fn fold<R: RangeBounds<usize>>(&self, range: R) -> G::Element {
+        let (l, r) = get_lr(range, self.len());
+        self.group
+            .op(&self.group.inv(&self.prefix[l]), &self.prefix[r])
+    }
+}
+
+/// 2次元累積和。長方形領域の和をO(1)で求める。
+#[derive(Debug, Clone)]
+pub struct CumulativeSum2D<G: AbelianGroup> {
+    group: G,
+    h: usize,
+    w: usize,
+    /// `(h + 1) * (w + 1)`の表で、`(i, j)`には`[0, i) × [0, j)`の和が入る
+    prefix: Vec<G::Element>,
+}
+
+impl<G: AbelianGroup> CumulativeSum2D<G> {
+    /// すべての行の長さが等しい必要がある。
+    pub fn new<I, Row>(group: G, grid: I) -> Self
+    where
+        I: IntoIterator<Item = Row>,
+        Row: IntoIterator<Item = G::Element>,
+    {
+        let mut prefix = Vec::new();
+        let mut h = 0;
+        let mut w = 0;
+        for row in grid {
+            let start = prefix.len();
+            let mut acc = group.identity();
+            prefix.push(acc.clone());
+            for x in row {
+                group.op_assign(&mut acc, &x);
+                prefix.push(acc.clone());
+            }
+            if h == 0 {
+                w = prefix.len() - start - 1;
+                prefix.splice(0..0, std::iter::repeat_n(group.identity(), w + 1));
+            } else {
+                assert_eq!(prefix.len() - start, w + 1, "grid must be rectangular.");
+            }
+            h += 1;
+        }
+        if h == 0 {
+            prefix.push(group.identity());
+        }
+        for i in w + 1..prefix.len() {
+            let above = prefix[i - w - 1].clone();
+            group.op_assign(&mut prefix[i], &above);
+        }
+        Self {
+            group,
+            h,
+            w,
+            prefix,
+        }
+    }
+
+    pub fn height(&self) -> usize {
+        self.h
+    }
+
+    pub fn width(&self) -> usize {
+        self.w
+    }
+
+    /// `rows × cols`の長方形領域の和
+    pub fn fold<R1, R2>(&self, rows: R1, cols: R2) -> G::Element
+    where
+        R1: RangeBounds<usize>,
+        R2: RangeBounds<usize>,
+    {
+        let (r0, r1) = get_lr(rows, self.h);
+        let (c0, c1) = get_lr(cols, self.w);
+        let p = |i: usize, j: usize| &self.prefix[i * (self.w + 1) + j];
+        let pos = self.group.op(p(r1, c1), p(r0, c0));
+        let neg = self.group.op(p(r0, c1), p(r1, c0));
+        self.group.op_inv(&pos, &neg)
+    }
+}
+
+/// 多次元累積和。直方体領域の和を、次元を`d`としてO(2^d)で求める。
+#[derive(Debug, Clone)]
+pub struct CumulativeSumND<G: AbelianGroup> {
+    group: G,
+    shape: Vec<usize>,
+    /// 各次元の大きさに1を足した表の行優先での添字の幅
+    strides: Vec<usize>,
+    prefix: Vec<G::Element>,
+}
+
+impl<G: AbelianGroup> CumulativeSumND<G> {
+    /// `data`は`shape`の形の配列を行優先で並べたもの。
+    pub fn new(group: G, shape: &[usize], data: Vec<G::Element>) -> Self {
+        assert_eq!(
+            data.len(),
+            shape.iter().product::<usize>(),
+            "data length does not match shape."
+        );
+        let mut strides = vec![1; shape.len()];
+        for k in (0..shape.len().saturating_sub(1)).rev() {
+            strides[k] = strides[k + 1] * (shape[k + 1] + 1);
+        }
+        let size = shape.iter().map(|&n| n + 1).product::<usize>();
+        let mut prefix = vec![group.identity(); size];
+        // 各座標に1を足した位置に値を置く
+        let offset = strides.iter().sum::<usize>();
+        let mut index = vec![0; shape.len()];
+        let mut pos = offset;
+        for x in data {
+            prefix[pos] = x;
+            for k in (0..shape.len()).rev() {
+                index[k] += 1;
+                pos += strides[k];
+                if index[k] < shape[k] {
+                    break;
+                }
+                index[k] = 0;
+                pos -= strides[k] * shape[k];
+            }
+        }
+        for (k, &stride) in strides.iter().enumerate() {
+            for i in stride..size {
+                if (i / stride) % (shape[k] + 1) != 0 {
+                    let prev = prefix[i - stride].clone();
+                    group.op_assign(&mut prefix[i], &prev);
+                }
+            }
+        }
+        Self {
+            group,
+            shape: shape.to_vec(),
+            strides,
+            prefix,
+        }
+    }
+
+    pub fn shape(&self) -> &[usize] {
+        &self.shape
+    }
+
+    /// `ranges[0] × ranges[1] × ...`の直方体領域の和
+    pub fn fold(&self, ranges: &[Range<usize>]) -> G::Element {
+        assert_eq!(ranges.len(), self.shape.len(), "dimension mismatch.");
+        let bounds = ranges
+            .iter()
+            .zip(&self.shape)
+            .map(|(range, &n)| get_lr(range.clone(), n))
+            .collect::<Vec<_>>();
+        let mut pos = self.group.identity();
+        let mut neg = self.group.identity();
+        for mask in 0..1usize << bounds.len() {
+            let i = bounds
+                .iter()
+                .zip(&self.strides)
+                .enumerate()
+                .map(|(k, (&(l, r), &stride))| (if mask >> k & 1 == 1 { l } else { r }) * stride)
+                .sum::<usize>();
+            if mask.count_ones() % 2 == 0 {
+                self.group.op_assign(&mut pos, &self.prefix[i]);
+            } else {
+                self.group.op_assign(&mut neg, &self.prefix[i]);
+            }
+        }
+        self.group.op_inv(&pos, &neg)
+    }
+}
+
+/// 差分配列(いもす法)。区間への加算をO(1)で行い、最後にO(n)で各要素の値を求める。
+#[derive(Debug, Clone)]
+pub struct DifferenceArray<G: AbelianGroup> {
+    group: G,
+    diff: Vec<G::Element>,
+}
+
+impl<G: AbelianGroup> DifferenceArray<G> {
+    /// 長さ`n`で、すべての要素が単位元の配列を作る。
+    pub fn new(group: G, n: usize) -> Self {
+        Self {
+            diff: vec![group.identity(); n + 1],
+            group,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.diff.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// `range`に含まれる要素に`x`を加える。
+    pub fn add<R: RangeBounds<usize>>(&mut self, range: R, x: &G::Element) {
+        let (l, r) = get_lr(range, self.len());
+        self.group.op_assign(&mut self.diff[l], x);
+        self.group.op_inv_assign(&mut self.diff[r], x);
+    }
+
+    /// 各要素の値を求める。
+    pub fn build(&self) -> Vec<G::Element> {
+        let mut acc = self.group.identity();
+        self.diff[..self.len()]
+            .iter()
+            .map(|d| {
+                self.group.op_assign(&mut acc, d);
+                acc.clone()
+            })
+            .collect()
+    }
+}
+
+/// 2次元の差分配列(いもす法)。長方形領域への加算をO(1)で行い、最後にO(hw)で各要素の値を求める。
+#[derive(Debug, Clone)]
+pub struct DifferenceArray2D<G: AbelianGroup> {
+    group: G,
+    h: usize,
+    w: usize,
+    diff: Vec<G::Element>,
+}
+
+impl<G: AbelianGroup> DifferenceArray2D<G> {
+    /// `h × w`で、すべての要素が単位元の配列を作る。
+    pub fn new(group: G, h: usize, w: usize) -> Self {
+        Self {
+            diff: vec![group.identity(); (h + 1) * (w + 1)],
+            group,
+            h,
+            w,
+        }
+    }
+
+    pub fn height(&self) -> usize {
+        self.h
+    }
+
+    pub fn width(&self) -> usize {
+        self.w
+    }
+
+    /// `rows × cols`の長方形領域に含まれる要素に`x`を加える。
+    pub fn add<R1, R2>(&mut self, rows: R1, cols: R2, x: &G::Element)
+    where
+        R1: RangeBounds<usize>,
+        R2: RangeBounds<usize>,
+    {
+        let (r0, r1) = get_lr(rows, self.h);
+        let (c0, c1) = get_lr(cols, self.w);
+        let w = self.w + 1;
+        self.group.op_assign(&mut self.diff[r0 * w + c0], x);
+        self.group.op_inv_assign(&mut self.diff[r0 * w + c1], x);
+        self.group.op_inv_assign(&mut self.diff[r1 * w + c0], x);
+        self.group.op_assign(&mut self.diff[r1 * w + c1], x);
+    }
+
+    /// 各要素の値を求める。
+    pub fn build(&self) -> Vec<Vec<G::Element>> {
+        let mut acc = self.diff.clone();
+        let w = self.w + 1;
+        for i in 1..acc.len() {
+            if i % w != 0 {
+                let left = acc[i - 1].clone();
+                self.group.op_assign(&mut acc[i], &left);
+            }
+        }
+        for i in w..acc.len() {
+            let above = acc[i - w].clone();
+            self.group.op_assign(&mut acc[i], &above);
+        }
+        acc.chunks(w)
+            .take(self.h)
+            .map(|row| row[..self.w].to_vec())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::IteratorExt;
+    use query::{GroupOp, Monoid, MonoidOp};
+    use rand::Rng;
+
+    fn add_group() -> impl AbelianGroup<Element = i64> {
+        GroupOp::new(MonoidOp::new(0, |a: &i64, b: &i64| a + b), |a: &i64| -a).assume_commutative()
+    }
+
+    #[test]
+    fn cumulative_fold_test() {
+        let v = [3, -1, 4, 1, -5, 9, 2, -6];
+        let cum = CumulativeFold::new(add_group(), v);
+        assert_eq!(cum.len(), v.len());
+        for l in 0..=v.len() {
+            for r in l..=v.len() {
+                assert_eq!(cum.fold(l..r), v[l..r].iter().sum::<i64>());
+            }
+        }
+        assert_eq!(cum.fold(..), 7);
+        assert_eq!(*cum.prefix(3), 6);
+        let cum = v.iter().copied().cumulative_fold(add_group());
+        assert_eq!(cum.fold(2..5), 0);
+
+        // 非可換群: 2x2の行列(mod 7)の積
+        type Mat = [[u32; 2]; 2];
+        let mul = |a: &Mat, b: &Mat| {
+            let mut c = [[0; 2]; 2];
+            for i in 0..2 {
+                for j in 0..2 {
+                    c[i][j] = (a[i][0] * b[0][j] + a[i][1] * b[1][j]) % 7;
+                }
+            }
+            c
+        };
+        let inv = |a: &Mat| {
+            let det = (a[0][0] * a[1][1] + 7 * 7 - a[0][1] * a[1][0]) % 7;
+            let det_inv = (1..7).find(|&x| det * x % 7 == 1).unwrap();
+            [
+                [a[1][1] * det_inv % 7, (7 - a[0][1]) * det_inv % 7],
+                [(7 - a[1][0]) * det_inv % 7, a[0][0] * det_inv % 7],
+            ]
+        };
+        let group = GroupOp::new(MonoidOp::new([[1, 0], [0, 1]], mul), inv);
+        let mut rng = rand::thread_rng();
+        let v = (0..20)
+            .map(|_| loop {
+                let a = [[0; 2]; 2].map(|row| row.map(|_: u32| rng.gen_range(0..7)));
+                if (a[0][0] * a[1][1] + 49 - a[0][1] * a[1][0]) % 7 != 0 {
+                    break a;
+                }
+            })
+            .collect::<Vec<_>>();
+        let cum = CumulativeFold::new(&group, v.iter().copied());
+        for l in 0..=v.len() {
+            for r in l..=v.len() {
+                let expected = v[l..r].iter().fold(group.identity(), |acc, x| mul(&acc, x));
+                assert_eq!(cum.fold(l..r), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn cumulative_sum_2d_test() {
+        let mut rng = rand::thread_rng();
+        let (h, w) = (7, 5);
+        let grid = (0..h)
+            .map(|_| {
+                (0..w)
+                    .map(|_| rng.gen_range(-100..100))
+                    .collect::<Vec<i64>>()
+            })
+            .collect::<Vec<_>>();
+        let cum = CumulativeSum2D::new(add_group(), grid.clone());
+        assert_eq!((cum.height(), cum.width()), (h, w));
+        for r0 in 0..=h {
+            for r1 in r0..=h {
+                for c0 in 0..=w {
+                    for c1 in c0..=w {
+                        let expected = grid[r0..r1]
+                            .iter()
+                            .map(|row| row[c0..c1].iter().sum::<i64>())
+                            .sum::<i64>();
+                        assert_eq!(cum.fold(r0..r1, c0..c1), expected);
+                    }
+                }
+            }
+        }
+        assert_eq!(cum.fold(.., ..), grid.iter().flatten().sum::<i64>());
+        let empty = CumulativeSum2D::new(add_group(), Vec::<Vec<i64>>::new());
+        assert_eq!(empty.fold(.., ..), 0);
+        let no_cols = CumulativeSum2D::new(add_group(), vec![vec![]; 3]);
+        assert_eq!(no_cols.fold(1..3, ..), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn cumulative_sum_2d_ragged() {
+        CumulativeSum2D::new(add_group(), vec![vec![1, 2], vec![3]]);
+    }
+
+    #[test]
+    fn cumulative_sum_nd_test() {
+        let mut rng = rand::thread_rng();
+        let shape = [3, 4, 2];
+        let data = (0..24)
+            .map(|_| rng.gen_range(-100..100))
+            .collect::<Vec<i64>>();
+        let cum = CumulativeSumND::new(add_group(), &shape, data.clone());
+        assert_eq!(cum.shape(), shape);
+        let at = |i: usize, j: usize, k: usize| data[(i * 4 + j) * 2 + k];
+        for _ in 0..200 {
+            let ranges = shape.map(|n| {
+                let l = rng.gen_range(0..=n);
+                l..rng.gen_range(l..=n)
+            });
+            let mut expected = 0;
+            for i in ranges[0].clone() {
+                for j in ranges[1].clone() {
+                    for k in ranges[2].clone() {
+                        expected += at(i, j, k);
+                    }
+                }
+            }
+            assert_eq!(cum.fold(&ranges), expected);
+        }
+        let grid = (0..3)
+            .map(|i| (0..4).map(|j| at(i, j, 0)).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let cum2 = CumulativeSum2D::new(add_group(), grid.clone());
+        let flat = grid.into_iter().flatten().collect();
+        let cum = CumulativeSumND::new(add_group(), &[3, 4], flat);
+        assert_eq!(cum.fold(&[1..3, 0..2]), cum2.fold(1..3, 0..2));
+        let cum = CumulativeSumND::new(add_group(), &[], vec![5]);
+        assert_eq!(cum.fold(&[]), 5);
+    }
+
+    #[test]
+    fn difference_array_test() {
+        let mut rng = rand::thread_rng();
+        let n = 30;
+        let mut imos = DifferenceArray::new(add_group(), n);
+        let mut expected = vec![0; n];
+        for _ in 0..100 {
+            let l = rng.gen_range(0..=n);
+            let r = rng.gen_range(l..=n);
+            let x = rng.gen_range(-100..100);
+            imos.add(l..r, &x);
+            expected[l..r].iter_mut().for_each(|y| *y += x);
+        }
+        imos.add(.., &1);
+        expected.iter_mut().for_each(|y| *y += 1);
+        assert_eq!(imos.build(), expected);
+
+        let (h, w) = (6, 8);
+        let mut imos = DifferenceArray2D::new(add_group(), h, w);
+        let mut expected = vec![vec![0; w]; h];
+        for _ in 0..100 {
+            let r0 = rng.gen_range(0..=h);
+            let r1 = rng.gen_range(r0..=h);
+            let c0 = rng.gen_range(0..=w);
+            let c1 = rng.gen_range(c0..=w);
+            let x = rng.gen_range(-100..100);
+            imos.add(r0..r1, c0..c1, &x);
+            for row in &mut expected[r0..r1] {
+                row[c0..c1].iter_mut().for_each(|y| *y += x);
+            }
+        }
+        assert_eq!(imos.build(), expected);
+    }
+}
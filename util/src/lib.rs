@@ -1,5 +1,11 @@
 pub mod algorithm;
+pub mod cumulative;
 pub mod modulo;
+pub mod search;
+pub mod sliding_window;
 
 pub use modulo::*;
 pub use algorithm::*;
+pub use cumulative::*;
+pub use search::*;
+pub use sliding_window::*;
@@ -1,33 +1,44 @@
+pub mod combinatorics;
+pub mod convolution;
+pub mod dynamic;
+pub mod fps;
+pub mod modint64;
+pub use combinatorics::Combinatorics;
+pub use convolution::{convolution, convolution_arbitrary_mod, convolution_naive};
+pub use dynamic::{Barrett, DynModInt};
+pub use fps::FormalPowerSeries;
+pub use modint64::ModInt64;
+
 use std::{
+    error::Error,
     fmt::{self, Display},
-    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign},
+    iter::{Product, Sum},
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+    str::FromStr,
 };
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, PartialOrd, Ord, Hash)]
 pub struct ModInt<const MOD: u32>(u32);
-const fn check_primary<const M: u32>() -> bool {
-    match M {
-        0 => false,
-        1 => false,
-        2 => true,
-        _ => {
-            if M % 2 == 0 {
-                return false;
-            }
-            let mut i = 3;
-            while i * i <= M {
-                if M % i == 0 {
-                    return false;
-                }
-                i += 2;
-            }
-            true
-        }
+/// `a * x ≡ 1 (mod m)`となる`0 <= x < m`を拡張ユークリッドの互除法で求める。
+/// `a < m`である必要がある。
+/// `ModInt`、`DynModInt`、`ModInt64`で共通に使う。
+const fn inv_gcd(a: u64, m: u64) -> Option<u64> {
+    let (mut r0, mut r1) = (m as i128, a as i128);
+    let (mut x0, mut x1) = (0i128, 1i128);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (x0, x1) = (x1, x0 - q * x1);
+    }
+    if r0 == 1 {
+        Some(x0.rem_euclid(m as i128) as u64)
+    } else {
+        None
     }
 }
 
 impl<const MOD: u32> ModInt<MOD> {
-    const MOD_IS_PRIME: bool = check_primary::<MOD>();
+    const MOD_IS_PRIME: bool = montgomery_modular::is_prime_u32(MOD);
 
     pub const fn new(x: i64) -> Self {
         Self(x.rem_euclid(MOD as i64) as u32)
@@ -59,7 +70,12 @@ impl<const MOD: u32> ModInt<MOD> {
         Self((self.0 as u64 * rhs.0 as u64 % MOD as u64) as u32)
     }
 
-    pub const fn pow(self, mut exp: u32) -> Self {
+    pub const fn pow(self, exp: u32) -> Self {
+        self.pow_u64(exp as u64)
+    }
+
+    /// 指数が`u32`に収まらない場合の[`ModInt::pow`]
+    pub const fn pow_u64(self, mut exp: u64) -> Self {
         let mut result = Self(1);
         let mut base = self;
         while exp > 0 {
@@ -72,11 +88,47 @@ impl<const MOD: u32> ModInt<MOD> {
         result
     }
 
+    /// `self`と法が互いに素でない場合は`None`を返す。
+    pub const fn checked_inv(self) -> Option<Self> {
+        match inv_gcd(self.0 as u64, MOD as u64) {
+            Some(x) => Some(Self(x as u32)),
+            None => None,
+        }
+    }
+
+    /// `self`と法が互いに素でない場合はパニックする。
     pub const fn inv(self) -> Self {
-        if !Self::MOD_IS_PRIME {
-            panic!("Cannot calculate the inverse of a number in a non-prime modulo.");
+        match self.checked_inv() {
+            Some(x) => x,
+            None => panic!("Cannot calculate the inverse of a number not coprime to the modulo."),
+        }
+    }
+
+    /// `rhs`と法が互いに素でない場合は`None`を返す。
+    pub const fn checked_div(self, rhs: Self) -> Option<Self> {
+        match rhs.checked_inv() {
+            Some(inv) => Some(self.mul_const(inv)),
+            None => None,
         }
-        self.pow(MOD - 2)
+    }
+
+    /// `x * x == self`となる`x`を1つ求める。平方剰余でない場合は`None`を返す。
+    /// 法は素数である必要がある。
+    pub fn sqrt(self) -> Option<Self> {
+        montgomery_modular::sqrt_mod(self.0, MOD).map(Self)
+    }
+
+    /// `self^x == y`となる最小の非負整数`x`を求める。存在しない場合は`None`を返す。
+    ///
+    /// `self`と法は互いに素でなくてもよい。O(√MOD)で計算する。
+    pub fn discrete_log(self, y: Self) -> Option<u64> {
+        montgomery_modular::discrete_log(self.0, y.0, MOD)
+    }
+
+    /// `x^k == self`となる`x`を1つ求める。存在しない場合は`None`を返す。
+    /// 法は素数である必要がある。
+    pub fn kth_root(self, k: u64) -> Option<Self> {
+        montgomery_modular::kth_root_mod(self.0, k, MOD).map(Self)
     }
 }
 
@@ -225,3 +277,316 @@ impl<const MOD: u32> Display for ModInt<MOD> {
         write!(f, "{}", self.0)
     }
 }
+
+#[cfg(feature = "segtree")]
+impl<const MOD: u32> segtree::operation::HasZero for ModInt<MOD> {
+    const ZERO: Self = Self::new(0);
+}
+
+#[cfg(feature = "segtree")]
+impl<const MOD: u32> segtree::operation::HasOne for ModInt<MOD> {
+    const ONE: Self = Self::new(1);
+}
+
+impl<const MOD: u32> Neg for ModInt<MOD> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self(0).sub_const(self)
+    }
+}
+
+macro_rules! impl_from_int {
+    ($via:ty; $($t:ty),*) => {
+        $(
+            impl<const MOD: u32> From<$t> for ModInt<MOD> {
+                fn from(value: $t) -> Self {
+                    Self((value as $via).rem_euclid(MOD as $via) as u32)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_int!(i64; i8, i16, i32, i64, isize);
+impl_from_int!(i128; i128);
+impl_from_int!(u32; u8, u16, u32);
+impl_from_int!(u64; u64, usize);
+impl_from_int!(u128; u128);
+
+/// `u32`と`u64`以外の整数型との演算
+macro_rules! impl_ops_int {
+    ($($t:ty),*) => {
+        $(
+            impl<const MOD: u32> Add<$t> for ModInt<MOD> {
+                type Output = Self;
+
+                fn add(self, rhs: $t) -> Self::Output {
+                    self.add_const(Self::from(rhs))
+                }
+            }
+
+            impl<const MOD: u32> AddAssign<$t> for ModInt<MOD> {
+                fn add_assign(&mut self, rhs: $t) {
+                    *self = *self + rhs;
+                }
+            }
+
+            impl<const MOD: u32> Sub<$t> for ModInt<MOD> {
+                type Output = Self;
+
+                fn sub(self, rhs: $t) -> Self::Output {
+                    self.sub_const(Self::from(rhs))
+                }
+            }
+
+            impl<const MOD: u32> SubAssign<$t> for ModInt<MOD> {
+                fn sub_assign(&mut self, rhs: $t) {
+                    *self = *self - rhs;
+                }
+            }
+
+            impl<const MOD: u32> Mul<$t> for ModInt<MOD> {
+                type Output = Self;
+
+                fn mul(self, rhs: $t) -> Self::Output {
+                    self.mul_const(Self::from(rhs))
+                }
+            }
+
+            impl<const MOD: u32> MulAssign<$t> for ModInt<MOD> {
+                fn mul_assign(&mut self, rhs: $t) {
+                    *self = *self * rhs;
+                }
+            }
+        )*
+    };
+}
+
+impl_ops_int!(i8, i16, i32, i64, i128, isize, u8, u16, u128, usize);
+
+impl<const MOD: u32> Sum for ModInt<MOD> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self(0), Self::add_const)
+    }
+}
+
+impl<'a, const MOD: u32> Sum<&'a Self> for ModInt<MOD> {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+impl<const MOD: u32> Product for ModInt<MOD> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self(1 % MOD), Self::mul_const)
+    }
+}
+
+impl<'a, const MOD: u32> Product<&'a Self> for ModInt<MOD> {
+    fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().product()
+    }
+}
+
+/// 文字列から`ModInt`への変換に失敗したときのエラー
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseModIntError {
+    /// 文字列が空、または符号のみ
+    Empty,
+    /// 数字以外の文字を含む
+    InvalidDigit,
+}
+
+impl Display for ParseModIntError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "cannot parse integer from empty string"),
+            Self::InvalidDigit => write!(f, "invalid digit found in string"),
+        }
+    }
+}
+
+impl Error for ParseModIntError {}
+
+/// 10進数の整数として解釈する。先頭に`+`または`-`を付けてもよく、桁数に制限はない。
+impl<const MOD: u32> FromStr for ModInt<MOD> {
+    type Err = ParseModIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (negative, digits) = match s.as_bytes() {
+            [b'-', rest @ ..] => (true, rest),
+            [b'+', rest @ ..] => (false, rest),
+            rest => (false, rest),
+        };
+        if digits.is_empty() {
+            return Err(ParseModIntError::Empty);
+        }
+        let mut x = 0u64;
+        for &d in digits {
+            if !d.is_ascii_digit() {
+                return Err(ParseModIntError::InvalidDigit);
+            }
+            x = (x * 10 + (d - b'0') as u64) % MOD as u64;
+        }
+        let x = Self(x as u32);
+        Ok(if negative { -x } else { x })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "segtree")]
+    fn segtree_test() {
+        use segtree::{operation, Segtree};
+
+        type M = ModInt<998244353>;
+        let mut segtree = [-1i64, 998244352, 5, 7]
+            .into_iter()
+            .map(M::from)
+            .collect::<Segtree<_, operation::Add<_>>>();
+        assert_eq!(segtree.query(..), M::new(10));
+        assert_eq!(segtree.query(..2), M::new(-2));
+        assert_eq!(segtree.query(2..2), M::new(0));
+        segtree.update(0, M::new(1));
+        assert_eq!(segtree.query(..), M::new(12));
+
+        let segtree = (1..=10u32)
+            .map(M::from)
+            .collect::<Segtree<_, operation::Mul<_>>>();
+        assert_eq!(segtree.query(..), M::new(3628800));
+        assert_eq!(segtree.query(3..3), M::new(1));
+    }
+
+    #[test]
+    fn inv_test() {
+        type M = ModInt<998244353>;
+        let a = M::new(123456789);
+        assert_eq!(a * a.inv(), M::new(1));
+        assert_eq!(M::new(0).checked_inv(), None);
+        assert_eq!((M::new(3) / M::new(2)) * M::new(2), M::new(3));
+
+        type C = ModInt<12>;
+        assert_eq!(C::new(5).inv(), C::new(5));
+        assert_eq!(C::new(7).checked_inv(), Some(C::new(7)));
+        assert_eq!(C::new(4).checked_inv(), None);
+        assert_eq!(C::new(1).checked_div(C::new(7)), Some(C::new(7)));
+        assert_eq!(C::new(1).checked_div(C::new(6)), None);
+        assert_eq!(C::new(1).checked_div(C::new(0)), None);
+    }
+
+    #[test]
+    fn sqrt_test() {
+        fn check<const MOD: u32>() {
+            for x in 0..MOD.min(2000) {
+                let x = ModInt::<MOD>::new(x as i64);
+                let is_square = (0..MOD).any(|y| ModInt::new(y as i64).pow(2) == x);
+                match x.sqrt() {
+                    Some(y) => assert_eq!(y * y, x),
+                    None => assert!(!is_square),
+                }
+            }
+        }
+        check::<2>();
+        check::<3>();
+        check::<13>();
+        check::<17>();
+        check::<97>();
+        check::<257>();
+        check::<7681>();
+        let x = ModInt::<998244353>::new(123456789).pow(2);
+        assert_eq!(x.sqrt().unwrap().pow(2), x);
+    }
+
+    #[test]
+    fn mod_is_prime_test() {
+        const { assert!(ModInt::<998244353>::MOD_IS_PRIME) };
+        const { assert!(ModInt::<2>::MOD_IS_PRIME) };
+        const { assert!(ModInt::<4294967291>::MOD_IS_PRIME) };
+        const { assert!(!ModInt::<1>::MOD_IS_PRIME) };
+        const { assert!(!ModInt::<{ u32::MAX }>::MOD_IS_PRIME) };
+        const { assert!(!ModInt::<{ 65521 * 65519 }>::MOD_IS_PRIME) };
+    }
+
+    #[test]
+    fn discrete_log_test() {
+        type M = ModInt<998244353>;
+        let g = M::new(3);
+        assert_eq!(g.discrete_log(g.pow(123456)), Some(123456));
+        assert_eq!(M::new(5).discrete_log(M::new(1)), Some(0));
+        assert_eq!(M::new(0).discrete_log(M::new(3)), None);
+        type C = ModInt<24>;
+        assert_eq!(C::new(2).discrete_log(C::new(16)), Some(4));
+        assert_eq!(C::new(2).discrete_log(C::new(3)), None);
+    }
+
+    #[test]
+    fn kth_root_test() {
+        type M = ModInt<998244353>;
+        for k in [1, 2, 3, 7, 1 << 23, 1 << 40] {
+            let a = M::new(31415926).pow_u64(k);
+            assert_eq!(a.kth_root(k).unwrap().pow_u64(k), a);
+        }
+        assert_eq!(M::new(3).kth_root(2), None);
+        assert_eq!(M::new(0).kth_root(5), Some(M::new(0)));
+    }
+
+    #[test]
+    fn conversion_test() {
+        type M = ModInt<998244353>;
+        assert_eq!(M::from(-1i8), M::new(-1));
+        assert_eq!(M::from(u64::MAX), M::new((u64::MAX % 998244353) as i64));
+        assert_eq!(M::from(i128::MIN).get() as i128, i128::MIN.rem_euclid(998244353));
+        assert_eq!(M::from(1usize << 40), M::new(1 << 40));
+        assert_eq!(-M::new(3), M::new(-3));
+        assert_eq!(-M::new(0), M::new(0));
+        assert_eq!(M::new(5) + -7i32, M::new(-2));
+        assert_eq!(M::new(5) - 7usize, M::new(-2));
+        assert_eq!(M::new(5) * -2i64, M::new(-10));
+        let mut x = M::new(1);
+        x += 3u8;
+        x -= -4i16;
+        x *= 3i128;
+        assert_eq!(x, M::new(24));
+        assert_eq!(
+            M::new(2).pow_u64(1 << 40),
+            M::new(2).pow_u64((1u64 << 40) % 998244352)
+        );
+        assert_eq!(M::new(3).pow_u64(5), M::new(3).pow(5));
+    }
+
+    #[test]
+    fn sum_product_test() {
+        type M = ModInt<7>;
+        let v = (1..=6).map(M::from).collect::<Vec<_>>();
+        assert_eq!(v.iter().sum::<M>(), M::new(0));
+        assert_eq!(v.iter().copied().product::<M>(), M::new(6));
+        assert_eq!(std::iter::empty::<M>().product::<M>(), M::new(1));
+        assert_eq!(std::iter::empty::<ModInt<1>>().product::<ModInt<1>>().get(), 0);
+    }
+
+    #[test]
+    fn parse_test() {
+        type M = ModInt<998244353>;
+        assert_eq!("123".parse::<M>(), Ok(M::new(123)));
+        assert_eq!("-1".parse::<M>(), Ok(M::new(-1)));
+        assert_eq!("+998244354".parse::<M>(), Ok(M::new(1)));
+        assert_eq!(
+            "100000000000000000000000000000".parse::<M>(),
+            Ok(M::new(10).pow(29))
+        );
+        assert_eq!("".parse::<M>(), Err(ParseModIntError::Empty));
+        assert_eq!("-".parse::<M>(), Err(ParseModIntError::Empty));
+        assert_eq!("12a".parse::<M>(), Err(ParseModIntError::InvalidDigit));
+        assert_eq!(" 1".parse::<M>(), Err(ParseModIntError::InvalidDigit));
+    }
+
+    #[test]
+    #[should_panic]
+    fn div_by_zero() {
+        let _ = ModInt::<998244353>::new(1) / ModInt::new(0);
+    }
+}
@@ -0,0 +1,258 @@
+use super::ModInt;
+
+/// 階乗とその逆元のテーブル
+///
+/// テーブルは必要になった時点で自動的に拡張される。
+/// `MOD`以上の階乗は逆元を持たないため、テーブルを`MOD`以上に拡張しようとするとパニックする。
+#[derive(Debug, Clone)]
+pub struct Combinatorics<const MOD: u32> {
+    fact: Vec<ModInt<MOD>>,
+    inv_fact: Vec<ModInt<MOD>>,
+}
+
+impl<const MOD: u32> Default for Combinatorics<MOD> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const MOD: u32> Combinatorics<MOD> {
+    pub fn new() -> Self {
+        Self {
+            fact: vec![ModInt::new(1)],
+            inv_fact: vec![ModInt::new(1)],
+        }
+    }
+
+    /// `0..=n`のテーブルを作る。
+    pub fn with_max(n: usize) -> Self {
+        let mut comb = Self::new();
+        comb.reserve(n);
+        comb
+    }
+
+    /// テーブルが`0..=n`を含むように拡張する。
+    pub fn reserve(&mut self, n: usize) {
+        let old_len = self.fact.len();
+        if n < old_len {
+            return;
+        }
+        let new_len = (n + 1).max((old_len * 2).min(MOD as usize));
+        for i in old_len..new_len {
+            let next = self.fact[i - 1] * i as u64;
+            self.fact.push(next);
+        }
+        let mut inv = self.fact[new_len - 1].checked_inv().unwrap_or_else(|| {
+            panic!(
+                "factorials up to {} are not invertible modulo {MOD}",
+                new_len - 1
+            )
+        });
+        self.inv_fact.resize(new_len, ModInt::new(0));
+        for i in (old_len..new_len).rev() {
+            self.inv_fact[i] = inv;
+            inv *= i as u64;
+        }
+    }
+
+    pub fn fact(&mut self, n: usize) -> ModInt<MOD> {
+        self.reserve(n);
+        self.fact[n]
+    }
+
+    pub fn inv_fact(&mut self, n: usize) -> ModInt<MOD> {
+        self.reserve(n);
+        self.inv_fact[n]
+    }
+
+    /// `n`の逆元を求める。`n`は正である必要がある。
+    pub fn inv(&mut self, n: usize) -> ModInt<MOD> {
+        assert_ne!(n, 0, "0 has no inverse");
+        self.reserve(n);
+        self.fact[n - 1] * self.inv_fact[n]
+    }
+
+    /// 二項係数`nCk`。`k > n`の場合は0を返す。
+    pub fn binom(&mut self, n: usize, k: usize) -> ModInt<MOD> {
+        if k > n {
+            return ModInt::new(0);
+        }
+        self.reserve(n);
+        self.fact[n] * self.inv_fact[k] * self.inv_fact[n - k]
+    }
+
+    /// 順列の数`nPk`。`k > n`の場合は0を返す。
+    pub fn perm(&mut self, n: usize, k: usize) -> ModInt<MOD> {
+        if k > n {
+            return ModInt::new(0);
+        }
+        self.reserve(n);
+        self.fact[n] * self.inv_fact[n - k]
+    }
+
+    /// 多項係数`(k_1 + k_2 + ... + k_m)! / (k_1! k_2! ... k_m!)`
+    pub fn multinomial(&mut self, ks: &[usize]) -> ModInt<MOD> {
+        let n = ks.iter().sum::<usize>();
+        self.reserve(n);
+        ks.iter()
+            .fold(self.fact[n], |acc, &k| acc * self.inv_fact[k])
+    }
+
+    /// カタラン数`C_n = (2n)! / (n! (n + 1)!)`
+    pub fn catalan(&mut self, n: usize) -> ModInt<MOD> {
+        self.reserve((2 * n).max(1));
+        self.fact[2 * n] * self.inv_fact[n] * self.inv_fact[n + 1]
+    }
+
+    /// 第1種スターリング数(符号なし)。`n`個の要素を`k`個の巡回置換に分ける方法の数。
+    ///
+    /// O(nk)で計算する。
+    pub fn stirling1(&mut self, n: usize, k: usize) -> ModInt<MOD> {
+        if k > n {
+            return ModInt::new(0);
+        }
+        // row[j] = [i, j]
+        let mut row = vec![ModInt::new(0); k + 1];
+        row[0] = ModInt::new(1);
+        for i in 0..n {
+            for j in (0..=k.min(i + 1)).rev() {
+                let prev = if j > 0 { row[j - 1] } else { ModInt::new(0) };
+                row[j] = row[j] * i as u64 + prev;
+            }
+        }
+        row[k]
+    }
+
+    /// 第2種スターリング数。`n`個の区別できる要素を`k`個の空でないグループに分ける方法の数。
+    ///
+    /// 包除原理を使い、O(k log n)で計算する。
+    pub fn stirling2(&mut self, n: usize, k: usize) -> ModInt<MOD> {
+        if k > n {
+            return ModInt::new(0);
+        }
+        let mut sum = ModInt::new(0);
+        for i in 0..=k {
+            let term = self.binom(k, i) * ModInt::new(i as i64).pow_u64(n as u64);
+            if (k - i) % 2 == 1 {
+                sum -= term;
+            } else {
+                sum += term;
+            }
+        }
+        sum * self.inv_fact(k)
+    }
+
+    /// Lucasの定理を使い、`n`が`MOD`以上の場合でも二項係数`nCk`を求める。
+    /// `MOD`は素数である必要がある。
+    pub fn binom_lucas(&mut self, mut n: u64, mut k: u64) -> ModInt<MOD> {
+        assert!(ModInt::<MOD>::MOD_IS_PRIME, "MOD must be prime.");
+        let p = MOD as u64;
+        let mut result = ModInt::new(1);
+        while k > 0 {
+            let (ni, ki) = ((n % p) as usize, (k % p) as usize);
+            if ki > ni {
+                return ModInt::new(0);
+            }
+            result *= self.binom(ni, ki);
+            n /= p;
+            k /= p;
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type M = ModInt<998244353>;
+
+    #[test]
+    fn binom_test() {
+        let mut comb = Combinatorics::<998244353>::new();
+        let mut pascal = vec![vec![M::new(1)]];
+        for n in 1..=60 {
+            let prev = &pascal[n - 1];
+            let mut row = vec![M::new(1); n + 1];
+            for k in 1..n {
+                row[k] = prev[k - 1] + prev[k];
+            }
+            pascal.push(row);
+        }
+        for n in (0..=60).rev() {
+            for k in 0..=n + 1 {
+                let expected = pascal[n].get(k).copied().unwrap_or(M::new(0));
+                assert_eq!(comb.binom(n, k), expected);
+                assert_eq!(comb.perm(n, k), expected * comb.fact(k));
+            }
+        }
+        assert_eq!(comb.fact(10), M::new(3628800));
+        assert_eq!(comb.inv_fact(10) * comb.fact(10), M::new(1));
+        assert_eq!(comb.inv(7) * M::new(7), M::new(1));
+        assert_eq!(comb.multinomial(&[2, 3, 4]), M::new(1260));
+        assert_eq!(comb.multinomial(&[]), M::new(1));
+    }
+
+    #[test]
+    fn catalan_test() {
+        let mut comb = Combinatorics::<998244353>::new();
+        let expected = [1, 1, 2, 5, 14, 42, 132, 429, 1430, 4862];
+        for (n, &c) in expected.iter().enumerate() {
+            assert_eq!(comb.catalan(n), M::new(c));
+        }
+    }
+
+    #[test]
+    fn stirling_test() {
+        let mut comb = Combinatorics::<998244353>::with_max(20);
+        let n = 12;
+        let mut s1 = vec![vec![M::new(0); n + 1]; n + 1];
+        let mut s2 = vec![vec![M::new(0); n + 1]; n + 1];
+        s1[0][0] = M::new(1);
+        s2[0][0] = M::new(1);
+        for i in 1..=n {
+            for j in 1..=i {
+                s1[i][j] = s1[i - 1][j] * (i - 1) as u64 + s1[i - 1][j - 1];
+                s2[i][j] = s2[i - 1][j] * j as u64 + s2[i - 1][j - 1];
+            }
+        }
+        for i in 0..=n {
+            for j in 0..=n {
+                assert_eq!(comb.stirling1(i, j), s1[i][j]);
+                assert_eq!(comb.stirling2(i, j), s2[i][j]);
+            }
+        }
+        assert_eq!(comb.stirling1(10, 3), M::new(1172700));
+        assert_eq!(comb.stirling2(10, 3), M::new(9330));
+    }
+
+    #[test]
+    fn lucas_test() {
+        fn binom_u128(n: u64, k: u64) -> u128 {
+            (0..k).fold(1u128, |acc, i| acc * (n - i) as u128 / (i + 1) as u128)
+        }
+        let mut comb = Combinatorics::<7>::new();
+        for n in 0..60 {
+            for k in 0..=n + 1 {
+                let expected = if k > n {
+                    0
+                } else {
+                    (binom_u128(n, k) % 7) as u32
+                };
+                assert_eq!(comb.binom_lucas(n, k).get(), expected);
+            }
+        }
+        let mut comb = Combinatorics::<13>::new();
+        for (n, k) in [(1_000_000, 5), (123456, 6), (999_999, 999_994)] {
+            let expected = (binom_u128(n, k.min(n - k)) % 13) as u32;
+            assert_eq!(comb.binom_lucas(n, k).get(), expected);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn not_invertible() {
+        let mut comb = Combinatorics::<7>::new();
+        comb.fact(7);
+    }
+}
@@ -0,0 +1,250 @@
+use super::ModInt;
+
+/// 素数`m`の原始根を求める。
+const fn primitive_root(m: u32) -> u32 {
+    if m == 2 {
+        return 1;
+    }
+    let mut factors = [0u32; 32];
+    let mut len = 0;
+    let mut x = m - 1;
+    let mut p = 2;
+    while p as u64 * p as u64 <= x as u64 {
+        if x.is_multiple_of(p) {
+            factors[len] = p;
+            len += 1;
+            while x.is_multiple_of(p) {
+                x /= p;
+            }
+        }
+        p += 1;
+    }
+    if x > 1 {
+        factors[len] = x;
+        len += 1;
+    }
+    let mut g = 2;
+    loop {
+        let mut i = 0;
+        while i < len {
+            if pow_mod(g, (m - 1) / factors[i], m) == 1 {
+                break;
+            }
+            i += 1;
+        }
+        if i == len {
+            return g;
+        }
+        g += 1;
+    }
+}
+
+const fn pow_mod(a: u32, mut exp: u32, m: u32) -> u32 {
+    let mut result = 1u64;
+    let mut base = a as u64 % m as u64;
+    let m = m as u64;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % m;
+        }
+        base = base * base % m;
+        exp >>= 1;
+    }
+    result as u32
+}
+
+struct NttInfo<const MOD: u32>;
+
+impl<const MOD: u32> NttInfo<MOD> {
+    const ROOT: ModInt<MOD> = {
+        assert!(ModInt::<MOD>::MOD_IS_PRIME, "MOD must be prime.");
+        ModInt::new(primitive_root(MOD) as i64)
+    };
+    /// 長さ`2^RANK2`までの数論変換ができる。
+    const RANK2: u32 = (MOD - 1).trailing_zeros();
+}
+
+/// 長さ`2 * half`の区間で使う回転因子`w^0, w^1, ..., w^(half - 1)`を`twiddle`に書き込む。
+fn twiddles<const MOD: u32>(half: usize, inverse: bool, twiddle: &mut Vec<ModInt<MOD>>) {
+    let w = NttInfo::<MOD>::ROOT.pow((MOD - 1) / (2 * half) as u32);
+    let w = if inverse { w.inv() } else { w };
+    twiddle.clear();
+    let mut x = ModInt::new(1);
+    for _ in 0..half {
+        twiddle.push(x);
+        x = x.mul_const(w);
+    }
+}
+
+/// 数論変換を行う。出力はビット反転した順序で並ぶ。
+fn ntt<const MOD: u32>(a: &mut [ModInt<MOD>]) {
+    let n = a.len();
+    let mut twiddle = Vec::with_capacity(n / 2);
+    let mut half = n / 2;
+    while half > 0 {
+        twiddles(half, false, &mut twiddle);
+        for block in a.chunks_exact_mut(2 * half) {
+            let (l, r) = block.split_at_mut(half);
+            for ((x, y), &w) in l.iter_mut().zip(r).zip(&twiddle) {
+                let (u, v) = (*x, *y);
+                *x = u + v;
+                *y = (u - v) * w;
+            }
+        }
+        half >>= 1;
+    }
+}
+
+/// `ntt`の逆変換。入力はビット反転した順序で並んでいる必要がある。
+fn intt<const MOD: u32>(a: &mut [ModInt<MOD>]) {
+    let n = a.len();
+    let mut twiddle = Vec::with_capacity(n / 2);
+    let mut half = 1;
+    while half < n {
+        twiddles(half, true, &mut twiddle);
+        for block in a.chunks_exact_mut(2 * half) {
+            let (l, r) = block.split_at_mut(half);
+            for ((x, y), &w) in l.iter_mut().zip(r).zip(&twiddle) {
+                let (u, v) = (*x, *y * w);
+                *x = u + v;
+                *y = u - v;
+            }
+        }
+        half <<= 1;
+    }
+    let n_inv = ModInt::new(n as i64).inv();
+    for x in a {
+        *x *= n_inv;
+    }
+}
+
+/// O(nm)で畳み込みを計算する。
+pub fn convolution_naive<const MOD: u32>(a: &[ModInt<MOD>], b: &[ModInt<MOD>]) -> Vec<ModInt<MOD>> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let mut c = vec![ModInt::new(0); a.len() + b.len() - 1];
+    for (i, &x) in a.iter().enumerate() {
+        for (j, &y) in b.iter().enumerate() {
+            c[i + j] += x * y;
+        }
+    }
+    c
+}
+
+const NAIVE_THRESHOLD: usize = 60;
+
+/// `c[k] = sum(a[i] * b[k - i])`となる`c`を求める。
+///
+/// `MOD`は素数で、`2^ceil(log2(a.len() + b.len() - 1))`が`MOD - 1`を割り切る必要がある。
+pub fn convolution<const MOD: u32>(a: &[ModInt<MOD>], b: &[ModInt<MOD>]) -> Vec<ModInt<MOD>> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    if a.len().min(b.len()) <= NAIVE_THRESHOLD {
+        return convolution_naive(a, b);
+    }
+    let len = a.len() + b.len() - 1;
+    let n = len.next_power_of_two();
+    assert!(
+        n.trailing_zeros() <= NttInfo::<MOD>::RANK2,
+        "length {len} is too long for modulo {MOD}"
+    );
+    let mut fa = a.to_vec();
+    let mut fb = b.to_vec();
+    fa.resize(n, ModInt::new(0));
+    fb.resize(n, ModInt::new(0));
+    ntt(&mut fa);
+    ntt(&mut fb);
+    for (x, &y) in fa.iter_mut().zip(&fb) {
+        *x *= y;
+    }
+    intt(&mut fa);
+    fa.truncate(len);
+    fa
+}
+
+/// 任意の法で畳み込みを計算する。
+///
+/// 3つの素数で畳み込んだ結果を中国剰余定理で復元するので、
+/// `min(a.len(), b.len()) * (MOD - 1)^2 < 167772161 * 469762049 * 754974721`である必要がある。
+pub fn convolution_arbitrary_mod<const MOD: u32>(
+    a: &[ModInt<MOD>],
+    b: &[ModInt<MOD>],
+) -> Vec<ModInt<MOD>> {
+    const M1: u32 = 754974721;
+    const M2: u32 = 167772161;
+    const M3: u32 = 469762049;
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    if a.len().min(b.len()) <= NAIVE_THRESHOLD {
+        return convolution_naive(a, b);
+    }
+    fn convert<const FROM: u32, const TO: u32>(a: &[ModInt<FROM>]) -> Vec<ModInt<TO>> {
+        a.iter().map(|x| ModInt::new(x.get() as i64)).collect()
+    }
+    let c1 = convolution::<M1>(&convert(a), &convert(b));
+    let c2 = convolution::<M2>(&convert(a), &convert(b));
+    let c3 = convolution::<M3>(&convert(a), &convert(b));
+
+    let m1_inv_m2 = ModInt::<M2>::new(M1 as i64).inv();
+    let m1_inv_m3 = ModInt::<M3>::new(M1 as i64).inv();
+    let m2_inv_m3 = ModInt::<M3>::new(M2 as i64).inv();
+    let m1 = ModInt::<MOD>::new(M1 as i64);
+    let m1m2 = m1 * M2;
+    c1.into_iter()
+        .zip(c2)
+        .zip(c3)
+        .map(|((x1, x2), x3)| {
+            // x = x1 + t2 * M1 + t3 * M1 * M2
+            let t2 = (x2 - x1.get()) * m1_inv_m2;
+            let t3 = ((x3 - x1.get()) * m1_inv_m3 - t2.get()) * m2_inv_m3;
+            ModInt::new(x1.get() as i64) + m1 * t2.get() + m1m2 * t3.get()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    #[test]
+    fn primitive_root_test() {
+        assert_eq!(primitive_root(2), 1);
+        assert_eq!(primitive_root(7), 3);
+        assert_eq!(primitive_root(998244353), 3);
+        assert_eq!(primitive_root(754974721), 11);
+        assert_eq!(primitive_root(167772161), 3);
+        assert_eq!(primitive_root(469762049), 3);
+    }
+
+    fn random_vec<const MOD: u32>(rng: &mut impl Rng, len: usize) -> Vec<ModInt<MOD>> {
+        (0..len).map(|_| ModInt::new(rng.gen_range(0..MOD) as i64)).collect()
+    }
+
+    #[test]
+    fn convolution_random() {
+        const MOD: u32 = 998244353;
+        let mut rng = rand::thread_rng();
+        for (n, m) in [(0, 5), (1, 1), (3, 100), (61, 61), (100, 200), (257, 1000), (1024, 1024)] {
+            let a = random_vec::<MOD>(&mut rng, n);
+            let b = random_vec::<MOD>(&mut rng, m);
+            assert_eq!(convolution(&a, &b), convolution_naive(&a, &b));
+        }
+    }
+
+    #[test]
+    fn convolution_arbitrary_mod_random() {
+        let mut rng = rand::thread_rng();
+        for (n, m) in [(1, 1), (61, 61), (100, 200), (1000, 777)] {
+            let a = random_vec::<1_000_000_007>(&mut rng, n);
+            let b = random_vec::<1_000_000_007>(&mut rng, m);
+            assert_eq!(convolution_arbitrary_mod(&a, &b), convolution_naive(&a, &b));
+            let a = random_vec::<{ u32::MAX >> 1 }>(&mut rng, n);
+            let b = random_vec::<{ u32::MAX >> 1 }>(&mut rng, m);
+            assert_eq!(convolution_arbitrary_mod(&a, &b), convolution_naive(&a, &b));
+        }
+    }
+}
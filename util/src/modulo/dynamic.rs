@@ -0,0 +1,325 @@
+use std::{
+    cell::Cell,
+    fmt::{self, Display},
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign},
+};
+
+/// 実行時に決まる法`m`での剰余をBarrett reductionで計算する。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Barrett {
+    m: u32,
+    /// `floor((2^64 - 1) / m)`
+    im: u64,
+}
+
+impl Barrett {
+    pub const fn new(m: u32) -> Self {
+        assert!(m != 0, "modulus must be positive");
+        Self {
+            m,
+            im: u64::MAX / m as u64,
+        }
+    }
+
+    pub const fn modulus(&self) -> u32 {
+        self.m
+    }
+
+    /// `a % m`を計算する。
+    pub const fn reduce(&self, a: u64) -> u32 {
+        let m = self.m as u64;
+        let q = ((a as u128 * self.im as u128) >> 64) as u64;
+        // `q`は`a / m`より高々2小さいだけなので、`r < 3 * m`
+        let mut r = a - q * m;
+        if r >= m {
+            r -= m;
+        }
+        if r >= m {
+            r -= m;
+        }
+        r as u32
+    }
+
+    /// `(a * b) % m`を計算する。
+    pub const fn mul(&self, a: u32, b: u32) -> u32 {
+        self.reduce(a as u64 * b as u64)
+    }
+}
+
+thread_local! {
+    static BARRETT: Cell<Barrett> = const { Cell::new(Barrett::new(998244353)) };
+}
+
+/// 法をスレッドごとに実行時に設定する`ModInt`
+///
+/// 法は`DynModInt::set_modulus`で設定する。初期値は`998244353`。
+/// 法を変更する前に作った値を、変更後に使ってはいけない。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, PartialOrd, Ord, Hash)]
+pub struct DynModInt(u32);
+
+impl DynModInt {
+    pub fn set_modulus(m: u32) {
+        BARRETT.with(|b| b.set(Barrett::new(m)));
+    }
+
+    pub fn modulus() -> u32 {
+        Self::barrett().modulus()
+    }
+
+    fn barrett() -> Barrett {
+        BARRETT.with(Cell::get)
+    }
+
+    pub fn new(x: i64) -> Self {
+        Self(x.rem_euclid(Self::modulus() as i64) as u32)
+    }
+
+    pub const fn get(self) -> u32 {
+        self.0
+    }
+
+    pub fn pow(self, mut exp: u32) -> Self {
+        let barrett = Self::barrett();
+        let mut result = barrett.reduce(1);
+        let mut base = self.0;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = barrett.mul(result, base);
+            }
+            base = barrett.mul(base, base);
+            exp >>= 1;
+        }
+        Self(result)
+    }
+
+    /// `self`と法が互いに素でない場合は`None`を返す。
+    pub fn checked_inv(self) -> Option<Self> {
+        super::inv_gcd(self.0 as u64, Self::modulus() as u64).map(|x| Self(x as u32))
+    }
+
+    /// `self`と法が互いに素でない場合はパニックする。
+    pub fn inv(self) -> Self {
+        match self.checked_inv() {
+            Some(x) => x,
+            None => panic!("{} is not invertible modulo {}", self.0, Self::modulus()),
+        }
+    }
+
+    /// `rhs`と法が互いに素でない場合は`None`を返す。
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        rhs.checked_inv().map(|inv| self * inv)
+    }
+}
+
+impl Add for DynModInt {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let m = Self::modulus() as u64;
+        let sum = self.0 as u64 + rhs.0 as u64;
+        if sum >= m {
+            Self((sum - m) as u32)
+        } else {
+            Self(sum as u32)
+        }
+    }
+}
+
+impl Add<u32> for DynModInt {
+    type Output = Self;
+
+    fn add(self, rhs: u32) -> Self::Output {
+        self + Self(Self::barrett().reduce(rhs as u64))
+    }
+}
+
+impl Add<u64> for DynModInt {
+    type Output = Self;
+
+    fn add(self, rhs: u64) -> Self::Output {
+        self + Self(Self::barrett().reduce(rhs))
+    }
+}
+
+impl AddAssign for DynModInt {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl AddAssign<u32> for DynModInt {
+    fn add_assign(&mut self, rhs: u32) {
+        *self = *self + rhs;
+    }
+}
+
+impl AddAssign<u64> for DynModInt {
+    fn add_assign(&mut self, rhs: u64) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for DynModInt {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        let m = Self::modulus() as u64;
+        let diff = self.0 as u64 + m - rhs.0 as u64;
+        if diff >= m {
+            Self((diff - m) as u32)
+        } else {
+            Self(diff as u32)
+        }
+    }
+}
+
+impl Sub<u32> for DynModInt {
+    type Output = Self;
+
+    fn sub(self, rhs: u32) -> Self::Output {
+        self - Self(Self::barrett().reduce(rhs as u64))
+    }
+}
+
+impl Sub<u64> for DynModInt {
+    type Output = Self;
+
+    fn sub(self, rhs: u64) -> Self::Output {
+        self - Self(Self::barrett().reduce(rhs))
+    }
+}
+
+impl SubAssign for DynModInt {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl SubAssign<u32> for DynModInt {
+    fn sub_assign(&mut self, rhs: u32) {
+        *self = *self - rhs;
+    }
+}
+
+impl SubAssign<u64> for DynModInt {
+    fn sub_assign(&mut self, rhs: u64) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul for DynModInt {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self(Self::barrett().mul(self.0, rhs.0))
+    }
+}
+
+impl Mul<u32> for DynModInt {
+    type Output = Self;
+
+    fn mul(self, rhs: u32) -> Self::Output {
+        Self(Self::barrett().mul(self.0, rhs))
+    }
+}
+
+impl Mul<u64> for DynModInt {
+    type Output = Self;
+
+    fn mul(self, rhs: u64) -> Self::Output {
+        let barrett = Self::barrett();
+        Self(barrett.mul(self.0, barrett.reduce(rhs)))
+    }
+}
+
+impl MulAssign for DynModInt {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl MulAssign<u32> for DynModInt {
+    fn mul_assign(&mut self, rhs: u32) {
+        *self = *self * rhs;
+    }
+}
+
+impl MulAssign<u64> for DynModInt {
+    fn mul_assign(&mut self, rhs: u64) {
+        *self = *self * rhs;
+    }
+}
+
+impl Div for DynModInt {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        self * rhs.inv()
+    }
+}
+
+impl DivAssign for DynModInt {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+
+impl Display for DynModInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    #[test]
+    fn barrett_random() {
+        let mut rng = rand::thread_rng();
+        for m in [1, 2, 3, 998244353, 1_000_000_007, u32::MAX - 1, u32::MAX] {
+            let barrett = Barrett::new(m);
+            assert_eq!(barrett.reduce(u64::MAX), (u64::MAX % m as u64) as u32);
+            for _ in 0..10000 {
+                let a = rng.gen::<u64>();
+                assert_eq!(barrett.reduce(a), (a % m as u64) as u32);
+                let (a, b) = (rng.gen_range(0..m), rng.gen_range(0..m));
+                assert_eq!(barrett.mul(a, b), (a as u64 * b as u64 % m as u64) as u32);
+            }
+        }
+    }
+
+    #[test]
+    fn dyn_mod_int_test() {
+        DynModInt::set_modulus(12);
+        assert_eq!(DynModInt::modulus(), 12);
+        let a = DynModInt::new(-7);
+        let b = DynModInt::new(7);
+        assert_eq!(a.get(), 5);
+        assert_eq!((a + b).get(), 0);
+        assert_eq!((a - b).get(), 10);
+        assert_eq!((a * b).get(), 11);
+        assert_eq!((a + 20u32).get(), 1);
+        assert_eq!((a * 100u64).get(), 8);
+        assert_eq!(a.pow(3).get(), 5);
+        assert_eq!(a.inv().get(), 5);
+        assert_eq!((b / a).get(), 11);
+        assert_eq!(DynModInt::new(4).checked_inv(), None);
+        assert_eq!(b.checked_div(DynModInt::new(6)), None);
+        assert_eq!(b.checked_div(a), Some(b / a));
+        assert_eq!(b.to_string(), "7");
+
+        DynModInt::set_modulus(1_000_000_007);
+        let x = DynModInt::new(123456789);
+        assert_eq!((x * x.inv()).get(), 1);
+        assert_eq!(x.pow(1_000_000_006).get(), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn inv_panic() {
+        DynModInt::set_modulus(12);
+        DynModInt::new(4).inv();
+    }
+}
@@ -0,0 +1,564 @@
+use std::ops::{Add, AddAssign, Deref, DerefMut, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use super::{convolution, ModInt};
+
+/// `ModInt<MOD>`を係数とする形式的冪級数
+///
+/// `i`番目の要素が`x^i`の係数を表す。
+/// 積や`inv`などは`convolution`を使うので、`MOD`はNTTが使える素数である必要がある。
+#[derive(Debug, Clone, PartialEq, Eq, Default, Hash)]
+pub struct FormalPowerSeries<const MOD: u32> {
+    coef: Vec<ModInt<MOD>>,
+}
+
+impl<const MOD: u32> Deref for FormalPowerSeries<MOD> {
+    type Target = Vec<ModInt<MOD>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.coef
+    }
+}
+
+impl<const MOD: u32> DerefMut for FormalPowerSeries<MOD> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.coef
+    }
+}
+
+impl<const MOD: u32> From<Vec<ModInt<MOD>>> for FormalPowerSeries<MOD> {
+    fn from(coef: Vec<ModInt<MOD>>) -> Self {
+        Self { coef }
+    }
+}
+
+impl<const MOD: u32> FromIterator<ModInt<MOD>> for FormalPowerSeries<MOD> {
+    fn from_iter<I: IntoIterator<Item = ModInt<MOD>>>(iter: I) -> Self {
+        Self {
+            coef: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<const MOD: u32> FormalPowerSeries<MOD> {
+    const ZERO: ModInt<MOD> = ModInt::new(0);
+    const ONE: ModInt<MOD> = ModInt::new(1);
+
+    pub fn new(coef: Vec<ModInt<MOD>>) -> Self {
+        Self { coef }
+    }
+
+    pub fn into_vec(self) -> Vec<ModInt<MOD>> {
+        self.coef
+    }
+
+    /// 先頭`n`項を返す。足りない項は0で埋める。
+    pub fn prefix(&self, n: usize) -> Self {
+        let mut coef = self.coef[..n.min(self.len())].to_vec();
+        coef.resize(n, Self::ZERO);
+        Self { coef }
+    }
+
+    /// 末尾の0を取り除く。
+    pub fn shrink(&mut self) {
+        while self.coef.last() == Some(&Self::ZERO) {
+            self.coef.pop();
+        }
+    }
+
+    /// 多項式としての次数。零多項式の場合は`None`を返す。
+    pub fn degree(&self) -> Option<usize> {
+        self.coef.iter().rposition(|&c| c != Self::ZERO)
+    }
+
+    pub fn derivative(&self) -> Self {
+        self.coef
+            .iter()
+            .enumerate()
+            .skip(1)
+            .map(|(i, &c)| c * i as u32)
+            .collect()
+    }
+
+    pub fn integral(&self) -> Self {
+        let n = self.len();
+        let mut inv = vec![Self::ONE; n + 1];
+        for i in 2..=n {
+            inv[i] = Self::ZERO - inv[MOD as usize % i] * (MOD / i as u32);
+        }
+        std::iter::once(Self::ZERO)
+            .chain(self.coef.iter().zip(&inv[1..]).map(|(&c, &inv)| c * inv))
+            .collect()
+    }
+
+    /// `x`での値を求める。
+    pub fn evaluate(&self, x: ModInt<MOD>) -> ModInt<MOD> {
+        self.coef.iter().rev().fold(Self::ZERO, |acc, &c| acc * x + c)
+    }
+
+    /// `self * g ≡ 1 (mod x^n)`となる`g`を求める。
+    /// 定数項が0の場合はパニックする。
+    pub fn inv(&self, n: usize) -> Self {
+        assert!(
+            self.first().is_some_and(|&c| c != Self::ZERO),
+            "constant term must be nonzero"
+        );
+        let mut g = Self::new(vec![self[0].inv()]);
+        let mut m = 1;
+        while m < n {
+            m *= 2;
+            let mut h = Self::new(convolution(&self.prefix(m), &g)).prefix(m);
+            for c in h.iter_mut() {
+                *c = Self::ZERO - *c;
+            }
+            h[0] += 2u32;
+            g = Self::new(convolution(&g, &h)).prefix(m);
+        }
+        g.prefix(n)
+    }
+
+    /// `log(self) mod x^n`を求める。
+    /// 定数項は1である必要がある。
+    pub fn log(&self, n: usize) -> Self {
+        assert_eq!(self.first(), Some(&Self::ONE), "constant term must be 1");
+        if n == 0 {
+            return Self::default();
+        }
+        let d = Self::new(convolution(&self.derivative(), &self.inv(n))).prefix(n - 1);
+        d.integral()
+    }
+
+    /// `exp(self) mod x^n`を求める。
+    /// 定数項は0である必要がある。
+    pub fn exp(&self, n: usize) -> Self {
+        assert!(
+            self.first().is_none_or(|&c| c == Self::ZERO),
+            "constant term must be 0"
+        );
+        let mut g = Self::new(vec![Self::ONE]);
+        let mut m = 1;
+        while m < n {
+            m *= 2;
+            let mut h = self.prefix(m) - g.log(m);
+            h[0] += 1u32;
+            g = Self::new(convolution(&g, &h)).prefix(m);
+        }
+        g.prefix(n)
+    }
+
+    /// `self^k mod x^n`を求める。
+    pub fn pow(&self, k: u64, n: usize) -> Self {
+        if k == 0 {
+            let mut result = Self::new(vec![Self::ZERO; n]);
+            if n > 0 {
+                result[0] = Self::ONE;
+            }
+            return result;
+        }
+        let Some(i) = self.coef.iter().position(|&c| c != Self::ZERO) else {
+            return Self::new(vec![Self::ZERO; n]);
+        };
+        let shift = match (i as u64).checked_mul(k) {
+            Some(shift) if shift < n as u64 => shift as usize,
+            _ => return Self::new(vec![Self::ZERO; n]),
+        };
+        let c = self[i];
+        let c_inv = c.inv();
+        let g: Self = self[i..].iter().map(|&x| x * c_inv).collect();
+        let mut log = g.log(n - shift);
+        let k_mod = ModInt::<MOD>::new((k % MOD as u64) as i64);
+        for x in log.iter_mut() {
+            *x *= k_mod;
+        }
+        let c_pow = c.pow((k % (MOD as u64 - 1)) as u32);
+        std::iter::repeat_n(Self::ZERO, shift)
+            .chain(log.exp(n - shift).iter().map(|&x| x * c_pow))
+            .collect()
+    }
+
+    /// `g * g ≡ self (mod x^n)`となる`g`を求める。
+    /// 存在しない場合は`None`を返す。
+    pub fn sqrt(&self, n: usize) -> Option<Self> {
+        let Some(i) = self.coef.iter().position(|&c| c != Self::ZERO) else {
+            return Some(Self::new(vec![Self::ZERO; n]));
+        };
+        // `x^n`の倍数なら0が解になる
+        if i >= n {
+            return Some(Self::new(vec![Self::ZERO; n]));
+        }
+        if i % 2 == 1 {
+            return None;
+        }
+        let shift = i / 2;
+        let c = self[i];
+        let s = c.sqrt()?;
+        let c_inv = c.inv();
+        let f: Self = self[i..].iter().map(|&x| x * c_inv).collect();
+        let m = n - shift;
+        let inv2 = ModInt::new(2).inv();
+        let mut g = Self::new(vec![Self::ONE]);
+        let mut len = 1;
+        while len < m {
+            len *= 2;
+            let h = Self::new(convolution(&f.prefix(len), &g.inv(len))).prefix(len);
+            g = (g.prefix(len) + h).iter().map(|&x| x * inv2).collect();
+        }
+        Some(
+            std::iter::repeat_n(Self::ZERO, shift)
+                .chain(g.iter().take(m).map(|&x| x * s))
+                .collect(),
+        )
+    }
+
+    /// 多項式として割り算を行い、商と余りを返す。
+    /// `rhs`が零多項式の場合はパニックする。
+    pub fn div_rem(&self, rhs: &Self) -> (Self, Self) {
+        let rhs_degree = rhs.degree().expect("division by zero polynomial");
+        let mut lhs = self.clone();
+        lhs.shrink();
+        if lhs.len() <= rhs_degree {
+            return (Self::default(), lhs);
+        }
+        let k = lhs.len() - rhs_degree;
+        let lhs_rev: Self = lhs.iter().rev().take(k).copied().collect();
+        let rhs_rev: Self = rhs[..=rhs_degree].iter().rev().copied().collect();
+        let mut q = Self::new(convolution(&lhs_rev, &rhs_rev.inv(k))).prefix(k);
+        q.reverse();
+        let mut r = (lhs - Self::new(convolution(&q, &rhs[..=rhs_degree]))).prefix(rhs_degree);
+        r.shrink();
+        (q, r)
+    }
+
+    /// 各`xs[i]`での値を求める。
+    pub fn multipoint_evaluation(&self, xs: &[ModInt<MOD>]) -> Vec<ModInt<MOD>> {
+        if xs.is_empty() {
+            return Vec::new();
+        }
+        let tree = SubproductTree::new(xs);
+        let size = tree.size;
+        let mut rem = vec![Self::default(); 2 * size];
+        rem[1] = self.div_rem(&tree.nodes[1]).1;
+        for i in 2..size + xs.len() {
+            rem[i] = rem[i / 2].div_rem(&tree.nodes[i]).1;
+        }
+        rem[size..size + xs.len()]
+            .iter()
+            .map(|r| r.first().copied().unwrap_or(Self::ZERO))
+            .collect()
+    }
+
+    /// `f(xs[i]) = ys[i]`となる次数`xs.len()`未満の多項式`f`を求める。
+    /// `xs`の要素は相異なる必要がある。
+    pub fn interpolation(xs: &[ModInt<MOD>], ys: &[ModInt<MOD>]) -> Self {
+        assert_eq!(xs.len(), ys.len());
+        if xs.is_empty() {
+            return Self::default();
+        }
+        let tree = SubproductTree::new(xs);
+        let size = tree.size;
+        let weights = tree.nodes[1].derivative().multipoint_evaluation(xs);
+        let mut nodes = vec![Self::default(); 2 * size];
+        for (i, (&y, &w)) in ys.iter().zip(&weights).enumerate() {
+            nodes[size + i] = Self::new(vec![y / w]);
+        }
+        for i in (1..size).rev() {
+            let l = Self::new(convolution(&nodes[2 * i], &tree.nodes[2 * i + 1]));
+            let r = Self::new(convolution(&nodes[2 * i + 1], &tree.nodes[2 * i]));
+            nodes[i] = l + r;
+        }
+        let mut f = std::mem::take(&mut nodes[1]);
+        f.shrink();
+        f
+    }
+}
+
+/// 葉が`x - xs[i]`で、各頂点が子の積となる完全二分木
+struct SubproductTree<const MOD: u32> {
+    size: usize,
+    nodes: Vec<FormalPowerSeries<MOD>>,
+}
+
+impl<const MOD: u32> SubproductTree<MOD> {
+    fn new(xs: &[ModInt<MOD>]) -> Self {
+        let size = xs.len().next_power_of_two();
+        let mut nodes = vec![FormalPowerSeries::new(vec![ModInt::new(1)]); 2 * size];
+        for (i, &x) in xs.iter().enumerate() {
+            nodes[size + i] = FormalPowerSeries::new(vec![ModInt::new(0) - x, ModInt::new(1)]);
+        }
+        for i in (1..size).rev() {
+            nodes[i] = FormalPowerSeries::new(convolution(&nodes[2 * i], &nodes[2 * i + 1]));
+        }
+        Self { size, nodes }
+    }
+}
+
+impl<const MOD: u32> AddAssign<&Self> for FormalPowerSeries<MOD> {
+    fn add_assign(&mut self, rhs: &Self) {
+        if self.len() < rhs.len() {
+            self.resize(rhs.len(), Self::ZERO);
+        }
+        for (x, &y) in self.iter_mut().zip(rhs.iter()) {
+            *x += y;
+        }
+    }
+}
+
+impl<const MOD: u32> AddAssign for FormalPowerSeries<MOD> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self += &rhs;
+    }
+}
+
+impl<const MOD: u32> Add for FormalPowerSeries<MOD> {
+    type Output = Self;
+
+    fn add(mut self, rhs: Self) -> Self::Output {
+        self += &rhs;
+        self
+    }
+}
+
+impl<const MOD: u32> SubAssign<&Self> for FormalPowerSeries<MOD> {
+    fn sub_assign(&mut self, rhs: &Self) {
+        if self.len() < rhs.len() {
+            self.resize(rhs.len(), Self::ZERO);
+        }
+        for (x, &y) in self.iter_mut().zip(rhs.iter()) {
+            *x -= y;
+        }
+    }
+}
+
+impl<const MOD: u32> SubAssign for FormalPowerSeries<MOD> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self -= &rhs;
+    }
+}
+
+impl<const MOD: u32> Sub for FormalPowerSeries<MOD> {
+    type Output = Self;
+
+    fn sub(mut self, rhs: Self) -> Self::Output {
+        self -= &rhs;
+        self
+    }
+}
+
+impl<const MOD: u32> Mul for &FormalPowerSeries<MOD> {
+    type Output = FormalPowerSeries<MOD>;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        FormalPowerSeries::new(convolution(self, rhs))
+    }
+}
+
+impl<const MOD: u32> Mul for FormalPowerSeries<MOD> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        &self * &rhs
+    }
+}
+
+impl<const MOD: u32> MulAssign<&Self> for FormalPowerSeries<MOD> {
+    fn mul_assign(&mut self, rhs: &Self) {
+        *self = &*self * rhs;
+    }
+}
+
+impl<const MOD: u32> MulAssign for FormalPowerSeries<MOD> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self *= &rhs;
+    }
+}
+
+impl<const MOD: u32> Neg for FormalPowerSeries<MOD> {
+    type Output = Self;
+
+    fn neg(mut self) -> Self::Output {
+        for x in self.iter_mut() {
+            *x = Self::ZERO - *x;
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::convolution_naive;
+    use rand::Rng;
+
+    const MOD: u32 = 998244353;
+    type Fps = FormalPowerSeries<MOD>;
+    type M = ModInt<MOD>;
+
+    fn random_fps(rng: &mut impl Rng, len: usize) -> Fps {
+        (0..len).map(|_| M::new(rng.gen_range(0..MOD) as i64)).collect()
+    }
+
+    fn mul_naive(a: &Fps, b: &Fps, n: usize) -> Fps {
+        Fps::new(convolution_naive(a, b)).prefix(n)
+    }
+
+    fn inv_naive(f: &Fps, n: usize) -> Fps {
+        let c = f[0].inv();
+        let mut g = vec![M::new(0); n];
+        for i in 0..n {
+            let mut sum = if i == 0 { M::new(1) } else { M::new(0) };
+            for j in 1..=i.min(f.len() - 1) {
+                sum -= f[j] * g[i - j];
+            }
+            g[i] = sum * c;
+        }
+        Fps::new(g)
+    }
+
+    fn exp_naive(f: &Fps, n: usize) -> Fps {
+        // n * g_n = sum_{k=1}^{n} k * f_k * g_{n-k}
+        let mut g = vec![M::new(0); n];
+        if n > 0 {
+            g[0] = M::new(1);
+        }
+        for i in 1..n {
+            let mut sum = M::new(0);
+            for k in 1..=i.min(f.len().saturating_sub(1)) {
+                sum += f[k] * k as u32 * g[i - k];
+            }
+            g[i] = sum / M::new(i as i64);
+        }
+        Fps::new(g)
+    }
+
+    fn div_rem_naive(f: &Fps, g: &Fps) -> (Fps, Fps) {
+        let mut r = f.clone();
+        r.shrink();
+        let d = g.degree().unwrap();
+        let lead_inv = g[d].inv();
+        let mut q = vec![M::new(0); r.len().saturating_sub(d)];
+        for i in (d..r.len()).rev() {
+            let c = r[i] * lead_inv;
+            q[i - d] = c;
+            for j in 0..=d {
+                r[i - d + j] -= c * g[j];
+            }
+        }
+        r.shrink();
+        let mut q = Fps::new(q);
+        q.shrink();
+        (q, r)
+    }
+
+    #[test]
+    fn inv_test() {
+        let mut rng = rand::thread_rng();
+        for n in [1, 2, 7, 64, 100, 300] {
+            let mut f = random_fps(&mut rng, n);
+            f[0] = M::new(rng.gen_range(1..MOD) as i64);
+            assert_eq!(f.inv(n), inv_naive(&f, n));
+            assert_eq!(f.inv(n + 5), inv_naive(&f, n + 5));
+        }
+    }
+
+    #[test]
+    fn log_exp_test() {
+        let mut rng = rand::thread_rng();
+        for n in [1, 2, 7, 64, 100, 300] {
+            let mut f = random_fps(&mut rng, n);
+            f[0] = M::new(0);
+            let g = f.exp(n);
+            assert_eq!(g, exp_naive(&f, n));
+            assert_eq!(g.log(n), f);
+            // log(g) = integral(g' / g)
+            let d = mul_naive(&g.derivative(), &inv_naive(&g, n), n - 1);
+            assert_eq!(g.log(n), d.integral());
+        }
+    }
+
+    #[test]
+    fn pow_test() {
+        let mut rng = rand::thread_rng();
+        for n in [1, 5, 30, 100] {
+            for zeros in [0, 1, 3] {
+                let mut f = random_fps(&mut rng, n);
+                for c in f.iter_mut().take(zeros) {
+                    *c = M::new(0);
+                }
+                for k in [0, 1, 2, 5, 17] {
+                    let mut expected = Fps::new(vec![M::new(0); n]);
+                    expected[0] = M::new(1);
+                    for _ in 0..k {
+                        expected = mul_naive(&expected, &f, n);
+                    }
+                    assert_eq!(f.pow(k, n), expected);
+                }
+            }
+        }
+        let f = Fps::new(vec![M::new(0), M::new(1)]);
+        assert_eq!(f.pow(u64::MAX, 10), Fps::new(vec![M::new(0); 10]));
+    }
+
+    #[test]
+    fn sqrt_test() {
+        let mut rng = rand::thread_rng();
+        for n in [1, 5, 30, 100] {
+            for zeros in [0, 2, 4] {
+                let mut g = random_fps(&mut rng, n);
+                for c in g.iter_mut().take(zeros / 2) {
+                    *c = M::new(0);
+                }
+                let f = mul_naive(&g, &g, n);
+                let s = f.sqrt(n).unwrap();
+                assert_eq!(mul_naive(&s, &s, n), f);
+            }
+        }
+        // 3は998244353の平方非剰余
+        assert_eq!(Fps::new(vec![M::new(3), M::new(1)]).sqrt(5), None);
+        assert_eq!(Fps::new(vec![M::new(0), M::new(1)]).sqrt(5), None);
+        assert_eq!(Fps::new(vec![M::new(0); 4]).sqrt(3), Some(Fps::new(vec![M::new(0); 3])));
+        let f = Fps::new(vec![M::new(0), M::new(0), M::new(0), M::new(1)]);
+        assert_eq!(f.sqrt(2), Some(Fps::new(vec![M::new(0); 2])));
+        assert_eq!(f.sqrt(1), Some(Fps::new(vec![M::new(0); 1])));
+        assert_eq!(f.sqrt(3), Some(Fps::new(vec![M::new(0); 3])));
+        assert_eq!(f.sqrt(4), None);
+    }
+
+    #[test]
+    fn div_rem_test() {
+        let mut rng = rand::thread_rng();
+        for (n, m) in [(1, 1), (5, 3), (3, 5), (100, 1), (200, 70), (300, 299)] {
+            let f = random_fps(&mut rng, n);
+            let mut g = random_fps(&mut rng, m);
+            *g.last_mut().unwrap() = M::new(1);
+            let (q, r) = f.div_rem(&g);
+            assert_eq!((q.clone(), r.clone()), div_rem_naive(&f, &g));
+            let mut lhs = Fps::new(convolution_naive(&q, &g)) + r;
+            lhs.shrink();
+            let mut f = f;
+            f.shrink();
+            assert_eq!(lhs, f);
+        }
+    }
+
+    #[test]
+    fn multipoint_evaluation_test() {
+        let mut rng = rand::thread_rng();
+        for (n, m) in [(1, 1), (10, 3), (3, 10), (100, 100), (130, 257)] {
+            let f = random_fps(&mut rng, n);
+            let xs = random_fps(&mut rng, m);
+            let expected = xs.iter().map(|&x| f.evaluate(x)).collect::<Vec<_>>();
+            assert_eq!(f.multipoint_evaluation(&xs), expected);
+        }
+    }
+
+    #[test]
+    fn interpolation_test() {
+        let mut rng = rand::thread_rng();
+        for n in [1, 2, 10, 100, 129] {
+            let mut f = random_fps(&mut rng, n);
+            f.shrink();
+            let xs = (0..n).map(|i| M::new(i as i64 * 7 + 3)).collect::<Vec<_>>();
+            let ys = xs.iter().map(|&x| f.evaluate(x)).collect::<Vec<_>>();
+            assert_eq!(Fps::interpolation(&xs, &ys), f);
+        }
+    }
+}
@@ -0,0 +1,263 @@
+use std::{
+    fmt::{self, Display},
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign},
+};
+
+/// 64bitの法を扱う`ModInt`。乗算は`u128`で計算する。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, PartialOrd, Ord, Hash)]
+pub struct ModInt64<const MOD: u64>(u64);
+
+impl<const MOD: u64> ModInt64<MOD> {
+    pub const fn new(x: i64) -> Self {
+        Self((x as i128).rem_euclid(MOD as i128) as u64)
+    }
+
+    pub const fn get(self) -> u64 {
+        self.0
+    }
+
+    pub const fn add_const(self, rhs: Self) -> Self {
+        let sum = self.0 as u128 + rhs.0 as u128;
+        if sum >= MOD as u128 {
+            Self((sum - MOD as u128) as u64)
+        } else {
+            Self(sum as u64)
+        }
+    }
+
+    pub const fn sub_const(self, rhs: Self) -> Self {
+        if self.0 >= rhs.0 {
+            Self(self.0 - rhs.0)
+        } else {
+            Self(MOD - (rhs.0 - self.0))
+        }
+    }
+
+    pub const fn mul_const(self, rhs: Self) -> Self {
+        Self((self.0 as u128 * rhs.0 as u128 % MOD as u128) as u64)
+    }
+
+    pub const fn pow(self, mut exp: u64) -> Self {
+        let mut result = Self(1 % MOD);
+        let mut base = self;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = result.mul_const(base);
+            }
+            base = base.mul_const(base);
+            exp >>= 1;
+        }
+        result
+    }
+
+    /// `self`と法が互いに素でない場合は`None`を返す。
+    pub const fn checked_inv(self) -> Option<Self> {
+        match super::inv_gcd(self.0, MOD) {
+            Some(x) => Some(Self(x)),
+            None => None,
+        }
+    }
+
+    /// `self`と法が互いに素でない場合はパニックする。
+    pub const fn inv(self) -> Self {
+        match self.checked_inv() {
+            Some(x) => x,
+            None => panic!("Cannot calculate the inverse of a number not coprime to the modulo."),
+        }
+    }
+
+    /// `rhs`と法が互いに素でない場合は`None`を返す。
+    pub const fn checked_div(self, rhs: Self) -> Option<Self> {
+        match rhs.checked_inv() {
+            Some(inv) => Some(self.mul_const(inv)),
+            None => None,
+        }
+    }
+}
+
+impl<const MOD: u64> Add for ModInt64<MOD> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.add_const(rhs)
+    }
+}
+
+impl<const MOD: u64> Add<u32> for ModInt64<MOD> {
+    type Output = Self;
+
+    fn add(self, rhs: u32) -> Self::Output {
+        self.add_const(Self(rhs as u64 % MOD))
+    }
+}
+
+impl<const MOD: u64> Add<u64> for ModInt64<MOD> {
+    type Output = Self;
+
+    fn add(self, rhs: u64) -> Self::Output {
+        self.add_const(Self(rhs % MOD))
+    }
+}
+
+impl<const MOD: u64> AddAssign for ModInt64<MOD> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const MOD: u64> AddAssign<u32> for ModInt64<MOD> {
+    fn add_assign(&mut self, rhs: u32) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const MOD: u64> AddAssign<u64> for ModInt64<MOD> {
+    fn add_assign(&mut self, rhs: u64) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const MOD: u64> Sub for ModInt64<MOD> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.sub_const(rhs)
+    }
+}
+
+impl<const MOD: u64> Sub<u32> for ModInt64<MOD> {
+    type Output = Self;
+
+    fn sub(self, rhs: u32) -> Self::Output {
+        self.sub_const(Self(rhs as u64 % MOD))
+    }
+}
+
+impl<const MOD: u64> Sub<u64> for ModInt64<MOD> {
+    type Output = Self;
+
+    fn sub(self, rhs: u64) -> Self::Output {
+        self.sub_const(Self(rhs % MOD))
+    }
+}
+
+impl<const MOD: u64> SubAssign for ModInt64<MOD> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<const MOD: u64> SubAssign<u32> for ModInt64<MOD> {
+    fn sub_assign(&mut self, rhs: u32) {
+        *self = *self - rhs;
+    }
+}
+
+impl<const MOD: u64> SubAssign<u64> for ModInt64<MOD> {
+    fn sub_assign(&mut self, rhs: u64) {
+        *self = *self - rhs;
+    }
+}
+
+impl<const MOD: u64> Mul for ModInt64<MOD> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.mul_const(rhs)
+    }
+}
+
+impl<const MOD: u64> Mul<u32> for ModInt64<MOD> {
+    type Output = Self;
+
+    fn mul(self, rhs: u32) -> Self::Output {
+        Self((self.0 as u128 * rhs as u128 % MOD as u128) as u64)
+    }
+}
+
+impl<const MOD: u64> Mul<u64> for ModInt64<MOD> {
+    type Output = Self;
+
+    fn mul(self, rhs: u64) -> Self::Output {
+        Self((self.0 as u128 * rhs as u128 % MOD as u128) as u64)
+    }
+}
+
+impl<const MOD: u64> MulAssign for ModInt64<MOD> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl<const MOD: u64> MulAssign<u32> for ModInt64<MOD> {
+    fn mul_assign(&mut self, rhs: u32) {
+        *self = *self * rhs;
+    }
+}
+
+impl<const MOD: u64> MulAssign<u64> for ModInt64<MOD> {
+    fn mul_assign(&mut self, rhs: u64) {
+        *self = *self * rhs;
+    }
+}
+
+impl<const MOD: u64> Div for ModInt64<MOD> {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        self.mul_const(rhs.inv())
+    }
+}
+
+impl<const MOD: u64> DivAssign for ModInt64<MOD> {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+
+impl<const MOD: u64> Display for ModInt64<MOD> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(feature = "segtree")]
+impl<const MOD: u64> segtree::operation::HasZero for ModInt64<MOD> {
+    const ZERO: Self = Self::new(0);
+}
+
+#[cfg(feature = "segtree")]
+impl<const MOD: u64> segtree::operation::HasOne for ModInt64<MOD> {
+    const ONE: Self = Self::new(1);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic_test() {
+        const P: u64 = (1 << 61) - 1;
+        type M = ModInt64<P>;
+        let a = M::new(-1);
+        assert_eq!(a.get(), P - 1);
+        assert_eq!((a + a).get(), P - 2);
+        assert_eq!((M::new(1) - a).get(), 2);
+        assert_eq!((a * a).get(), 1);
+        assert_eq!((a + u64::MAX).get(), (u64::MAX % P + P - 1) % P);
+        assert_eq!(M::new(3).pow(P - 1).get(), 1);
+        let b = M::new(123456789123456789);
+        assert_eq!((b * b.inv()).get(), 1);
+        assert_eq!(b / b, M::new(1));
+        assert_eq!(b.to_string(), "123456789123456789");
+
+        type Big = ModInt64<{ u64::MAX }>;
+        let c = Big::new(-2);
+        assert_eq!(c.get(), u64::MAX - 2);
+        assert_eq!((c + c).get(), u64::MAX - 4);
+        assert_eq!((c * c).get(), 4);
+        assert_eq!(Big::new(2).pow(64).get(), 1);
+        assert_eq!(Big::new(3).checked_inv(), None);
+        assert_eq!(Big::new(2).checked_div(Big::new(2)), Some(Big::new(1)));
+    }
+}
@@ -0,0 +1,365 @@
+use std::ops::{Bound, RangeBounds, RangeInclusive};
+
+use number::traits::PrimInt;
+
+use crate::algorithm::upper_bound;
+
+/// 浮動小数点数の二分探索を`iterations`回行う。
+///
+/// `range`が`l..=r`で、`f`は`l`付近で`true`、`r`付近で`false`となる単調な関数とする。
+/// `f`が`true`から`false`に切り替わる境界の近似値を返す。
+/// 区間の幅は1回ごとに半分になるので、`f64`では100回程度で十分。
+pub fn upper_bound_f64_by_iter(
+    range: RangeInclusive<f64>,
+    iterations: usize,
+    mut f: impl FnMut(f64) -> bool,
+) -> f64 {
+    let (mut l, mut r) = range.into_inner();
+    for _ in 0..iterations {
+        let m = l + (r - l) / 2.0;
+        if f(m) {
+            l = m;
+        } else {
+            r = m;
+        }
+    }
+    l + (r - l) / 2.0
+}
+
+/// `f64`のビット表現の上で二分探索を行う。
+///
+/// `range`が`l..=r`で、返り値を`x`とすると、`l <= y < x`となる`y`において`f(y)`が`true`となり、
+/// `x <= y <= r`となる`y`において`f(y)`が`false`となる。
+/// すべて`true`の場合は`r`の次に大きい`f64`を返す。ただし`r`が正の無限大の場合は正の無限大を返す。
+/// 高々64回の評価で、表現可能な値の中で厳密な境界が求まる。`-0.0 < 0.0`として扱う。
+pub fn upper_bound_f64(range: RangeInclusive<f64>, mut f: impl FnMut(f64) -> bool) -> f64 {
+    let (l, r) = range.into_inner();
+    assert!(!l.is_nan() && !r.is_nan(), "range must not contain NaN.");
+    let key = upper_bound(f64_to_key(l)..=f64_to_key(r), |k| f(key_to_f64(k)));
+    if key > f64_to_key(f64::INFINITY) {
+        // 正の無限大の次はNaNになってしまう
+        return f64::INFINITY;
+    }
+    key_to_f64(key)
+}
+
+/// `f64`を大小関係を保って`u64`に写す。
+fn f64_to_key(x: f64) -> u64 {
+    let bits = x.to_bits();
+    if bits >> 63 == 1 {
+        !bits
+    } else {
+        bits | 1 << 63
+    }
+}
+
+fn key_to_f64(key: u64) -> f64 {
+    if key >> 63 == 1 {
+        f64::from_bits(key & !(1 << 63))
+    } else {
+        f64::from_bits(!key)
+    }
+}
+
+/// 単峰な関数`f`の最小値を与える整数`x`と`f(x)`を求める。
+///
+/// `f`は`range`で、最小値をとる点まで狭義単調減少、そこから狭義単調増加である必要がある。
+/// 最大値を求めたい場合は`std::cmp::Reverse`などで順序を反転させる。
+/// `f(m)`と`f(m + 1)`の比較による二分探索で、`f`の評価回数は`2 log2(r - l)`程度。
+pub fn ternary_search<T: PrimInt, U: PartialOrd>(
+    range: impl RangeBounds<T>,
+    mut f: impl FnMut(T) -> U,
+) -> (T, U) {
+    let (mut lo, mut hi) = inclusive_bounds(range);
+    while lo < hi {
+        (lo, hi) = halve(lo, hi, &mut f);
+    }
+    (lo, f(lo))
+}
+
+/// 単峰な関数`f`の最小値を与える整数`x`と`f(x)`を黄金分割探索(フィボナッチ探索)で求める。
+///
+/// 条件は[`ternary_search`]と同じ。前回の評価値を使い回すので、
+/// `f`の評価回数は`log_φ(r - l)`(`1.44 log2(r - l)`)程度で、`f`が重い場合に有利。
+pub fn golden_section_search<T: PrimInt, U: PartialOrd>(
+    range: impl RangeBounds<T>,
+    mut f: impl FnMut(T) -> U,
+) -> (T, U) {
+    let two = T::one() + T::one();
+    let (mut lo, mut hi) = inclusive_bounds(range);
+    // 区間の長さ以上のフィボナッチ数が`T`で表せるまで区間を半分にする
+    let (mut prev, mut cur) = loop {
+        if let Some(fib) = hi.checked_sub(lo).and_then(|span| fib_above(span)) {
+            break fib;
+        }
+        (lo, hi) = halve(lo, hi, &mut f);
+    };
+    // (prev, cur) = (F[k - 1], F[k])のとき、候補は`a..a + F[k] - 1`で、
+    // `a + F[k - 2] - 1`と`a + F[k - 1] - 1`を比較する。区間外は+∞とみなす。
+    let mut a = lo;
+    let mut probe = |a: T, offset: T| a.checked_add(offset).filter(|&x| x <= hi).map(&mut f);
+    let mut v1 = probe(a, cur - prev - T::one());
+    let mut v2 = if cur > two {
+        probe(a, prev - T::one())
+    } else {
+        None
+    };
+    while cur > two {
+        let pp = cur - prev;
+        let keep_left = match (&v1, &v2) {
+            (Some(x), Some(y)) => x <= y,
+            (_, None) => true,
+            (None, Some(_)) => false,
+        };
+        (prev, cur) = (pp, prev);
+        if keep_left {
+            v2 = v1;
+            v1 = if cur > two {
+                probe(a, cur - prev - T::one())
+            } else {
+                None
+            };
+        } else {
+            a += pp;
+            v1 = v2;
+            v2 = if cur > two {
+                probe(a, prev - T::one())
+            } else {
+                None
+            };
+        }
+    }
+    // 最後に残った候補`a`の値はv1かv2のどちらかに入っている
+    (a, v1.or(v2).unwrap())
+}
+
+/// `F[k] >= span + 2`となる最小の`(F[k - 1], F[k])`を求める。`T`で表せない場合は`None`。
+fn fib_above<T: PrimInt>(span: T) -> Option<(T, T)> {
+    let two = T::one() + T::one();
+    let (mut prev, mut cur) = (T::one(), two);
+    while cur - two < span {
+        (prev, cur) = (cur, prev.checked_add(cur)?);
+    }
+    Some((prev, cur))
+}
+
+/// 単峰な関数`f`の区間`[lo, hi]`を、最小値を含むように半分にする。
+fn halve<T: PrimInt, U: PartialOrd>(lo: T, hi: T, f: &mut impl FnMut(T) -> U) -> (T, T) {
+    let two = T::one() + T::one();
+    let m = match hi.checked_sub(lo) {
+        Some(d) => lo + d / two,
+        // 符号付き整数で`lo < 0 < hi`の場合
+        None => lo / two + hi / two,
+    };
+    if f(m + T::one()) >= f(m) {
+        (lo, m)
+    } else {
+        (m + T::one(), hi)
+    }
+}
+
+fn inclusive_bounds<T: PrimInt>(range: impl RangeBounds<T>) -> (T, T) {
+    let lo = match range.start_bound() {
+        Bound::Included(&l) => Some(l),
+        Bound::Excluded(&l) => l.checked_add(T::one()),
+        Bound::Unbounded => Some(T::min_value()),
+    };
+    let hi = match range.end_bound() {
+        Bound::Included(&r) => Some(r),
+        Bound::Excluded(&r) => r.checked_sub(T::one()),
+        Bound::Unbounded => Some(T::max_value()),
+    };
+    match (lo, hi) {
+        (Some(lo), Some(hi)) if lo <= hi => (lo, hi),
+        _ => panic!("range must not be empty."),
+    }
+}
+
+/// 単峰な関数`f`の最小値を与える実数`x`と`f(x)`を三分探索で求める。
+///
+/// 1回ごとに区間の幅が2/3倍になり、`f`を2回評価する。
+pub fn ternary_search_f64<U: PartialOrd>(
+    range: RangeInclusive<f64>,
+    iterations: usize,
+    mut f: impl FnMut(f64) -> U,
+) -> (f64, U) {
+    let (mut l, mut r) = range.into_inner();
+    for _ in 0..iterations {
+        let m1 = l + (r - l) / 3.0;
+        let m2 = r - (r - l) / 3.0;
+        if f(m1) <= f(m2) {
+            r = m2;
+        } else {
+            l = m1;
+        }
+    }
+    let x = l + (r - l) / 2.0;
+    (x, f(x))
+}
+
+/// 単峰な関数`f`の最小値を与える実数`x`と`f(x)`を黄金分割探索で求める。
+///
+/// 1回ごとに区間の幅が`1/φ = 0.618`倍になり、`f`を1回だけ評価する。
+pub fn golden_section_search_f64<U: PartialOrd>(
+    range: RangeInclusive<f64>,
+    iterations: usize,
+    mut f: impl FnMut(f64) -> U,
+) -> (f64, U) {
+    // 1 / φ
+    const INV_PHI: f64 = 0.618_033_988_749_894_9;
+    let (mut l, mut r) = range.into_inner();
+    let mut m1 = r - (r - l) * INV_PHI;
+    let mut m2 = l + (r - l) * INV_PHI;
+    let mut v1 = f(m1);
+    let mut v2 = f(m2);
+    for _ in 0..iterations {
+        if v1 <= v2 {
+            r = m2;
+            (m2, v2) = (m1, v1);
+            m1 = r - (r - l) * INV_PHI;
+            v1 = f(m1);
+        } else {
+            l = m1;
+            (m1, v1) = (m2, v2);
+            m2 = l + (r - l) * INV_PHI;
+            v2 = f(m2);
+        }
+    }
+    if v1 <= v2 {
+        (m1, v1)
+    } else {
+        (m2, v2)
+    }
+}
+
+/// `f(x)`が`false`となる`start`以上の最小の`x`を求める。
+///
+/// `f`は`start`以上で`true`から`false`に切り替わる単調な関数とする。
+/// 探索範囲を1, 2, 4, ...と広げてから二分探索するので、答えを`x`として`f`の評価回数はO(log(x - start))。
+/// `T`の最大値まで`true`の場合は`None`を返す。
+pub fn exponential_search<T: PrimInt>(start: T, mut f: impl FnMut(T) -> bool) -> Option<T> {
+    if !f(start) {
+        return Some(start);
+    }
+    let mut lo = start;
+    let mut step = T::one();
+    loop {
+        match lo.checked_add(step) {
+            Some(x) if f(x) => {
+                lo = x;
+                step = step.saturating_add(step);
+            }
+            Some(x) => return Some(upper_bound(lo + T::one()..x, f)),
+            None => {
+                let max = T::max_value();
+                return (lo < max && !f(max)).then(|| upper_bound(lo + T::one()..max, f));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cmp::Reverse;
+
+    #[test]
+    fn upper_bound_f64_test() {
+        let x = upper_bound_f64_by_iter(0.0..=2.0, 100, |x| x * x < 2.0);
+        assert!((x - 2f64.sqrt()).abs() < 1e-15);
+        let x = upper_bound_f64(0.0..=2.0, |x| x * x < 2.0);
+        assert!(x * x >= 2.0);
+        assert!(x.next_down() * x.next_down() < 2.0);
+        assert_eq!(upper_bound_f64(-1.0..=1.0, |x| x < -0.5), -0.5);
+        // -0.0 < 0.0 は false なので境界は -0.0
+        let x = upper_bound_f64(-1.0..=1.0, |x| x < 0.0);
+        assert_eq!(x.to_bits(), (-0f64).to_bits());
+        let x = upper_bound_f64(-1.0..=1.0, |x| x.is_sign_negative());
+        assert_eq!(x.to_bits(), 0f64.to_bits());
+        assert_eq!(upper_bound_f64(-1.0..=1.0, |_| false), -1.0);
+        assert_eq!(upper_bound_f64(-1.0..=1.0, |_| true), 1f64.next_up());
+        assert_eq!(
+            upper_bound_f64(0.0..=f64::INFINITY, |_| true),
+            f64::INFINITY
+        );
+        assert_eq!(upper_bound_f64(0.0..=f64::MAX, |_| true), f64::INFINITY);
+        assert_eq!(
+            upper_bound_f64(f64::NEG_INFINITY..=f64::INFINITY, |x| x < 1e300),
+            1e300
+        );
+        for x in [f64::NEG_INFINITY, -1.5, -0.0, 0.0, 1e-310, 3.0, f64::MAX] {
+            assert_eq!(key_to_f64(f64_to_key(x)).to_bits(), x.to_bits());
+            assert!(f64_to_key(x) < f64_to_key(x.next_up()));
+        }
+    }
+
+    #[test]
+    fn ternary_search_test() {
+        for l in -20..20i32 {
+            for r in l..20 {
+                for p in -25..25 {
+                    let f = |x: i32| (x - p) * (x - p);
+                    let expected = p.clamp(l, r);
+                    assert_eq!(ternary_search(l..=r, f), (expected, f(expected)));
+                    let mut calls = 0;
+                    let ans = golden_section_search(l..=r, |x| {
+                        calls += 1;
+                        f(x)
+                    });
+                    assert_eq!(ans, (expected, f(expected)), "{l} {r} {p}");
+                    assert!(calls <= 10, "{l} {r} {p} {calls}");
+                }
+            }
+        }
+        let f = |x: i64| (x as i128 - 123_456_789_012).pow(2);
+        assert_eq!(ternary_search(.., f).0, 123_456_789_012);
+        assert_eq!(golden_section_search(.., f).0, 123_456_789_012);
+        let f = |x: u8| Reverse((x as i32 - 200).abs());
+        assert_eq!(golden_section_search(.., f), (0, Reverse(200)));
+        let f = |x: u64| x.abs_diff(u64::MAX - 3);
+        assert_eq!(golden_section_search(.., f), (u64::MAX - 3, 0));
+        assert_eq!(ternary_search(5..6, f), (5, u64::MAX - 8));
+    }
+
+    #[test]
+    #[should_panic]
+    fn ternary_search_empty() {
+        ternary_search(3..3, |x: i32| x);
+    }
+
+    #[test]
+    fn ternary_search_f64_test() {
+        let f = |x: f64| (x - 1.5).powi(2) + 2.0;
+        let (x, y) = ternary_search_f64(-10.0..=10.0, 100, f);
+        assert!((x - 1.5).abs() < 1e-6 && (y - 2.0).abs() < 1e-12);
+        let mut calls = 0;
+        let (x, y) = golden_section_search_f64(-10.0..=10.0, 100, |x| {
+            calls += 1;
+            f(x)
+        });
+        assert!((x - 1.5).abs() < 1e-6 && (y - 2.0).abs() < 1e-12);
+        assert_eq!(calls, 102);
+        let (x, _) = golden_section_search_f64(0.0..=3.0, 100, |x: f64| -x.sin());
+        assert!((x - std::f64::consts::FRAC_PI_2).abs() < 1e-6);
+    }
+
+    #[test]
+    fn exponential_search_test() {
+        for start in -50..50i64 {
+            for ans in start..60 {
+                let mut calls = 0;
+                let res = exponential_search(start, |x| {
+                    calls += 1;
+                    x < ans
+                });
+                assert_eq!(res, Some(ans));
+                assert!(calls <= 2 * (64 - (ans - start + 1).leading_zeros()) + 2);
+            }
+        }
+        assert_eq!(exponential_search(0u64, |x| x < 1 << 40), Some(1 << 40));
+        assert_eq!(exponential_search(i8::MIN, |x| x < 127), Some(127));
+        assert_eq!(exponential_search(i8::MIN, |_| true), None);
+        assert_eq!(exponential_search(u8::MAX, |_| true), None);
+        assert_eq!(exponential_search(3u8, |x| x < u8::MAX), Some(u8::MAX));
+    }
+}
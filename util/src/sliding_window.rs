@@ -0,0 +1,168 @@
+use query::Monoid;
+
+/// Sliding Window Aggregation
+///
+/// 2つのスタックで作ったキューで、末尾への追加、先頭の削除、全体の積の取得をならしO(1)で行う。
+/// モノイドは可換でなくてもよく、積は先頭から順に掛けたものになる。
+#[derive(Debug, Clone)]
+pub struct SlidingWindowAggregation<M: Monoid> {
+    monoid: M,
+    /// `(値, その値から先頭側のスタックの底までの積)`
+    front: Vec<(M::Element, M::Element)>,
+    back: Vec<M::Element>,
+    back_fold: M::Element,
+}
+
+impl<M: Monoid> SlidingWindowAggregation<M> {
+    pub fn new(monoid: M) -> Self {
+        Self {
+            front: Vec::new(),
+            back: Vec::new(),
+            back_fold: monoid.identity(),
+            monoid,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.front.len() + self.back.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 末尾に`x`を追加する。
+    pub fn push(&mut self, x: M::Element) {
+        self.monoid.op_assign(&mut self.back_fold, &x);
+        self.back.push(x);
+    }
+
+    /// 先頭の要素を削除して返す。
+    pub fn pop(&mut self) -> Option<M::Element> {
+        if self.front.is_empty() {
+            let mut acc = self.monoid.identity();
+            while let Some(x) = self.back.pop() {
+                acc = self.monoid.op(&x, &acc);
+                self.front.push((x, acc.clone()));
+            }
+            self.back_fold = self.monoid.identity();
+        }
+        self.front.pop().map(|(x, _)| x)
+    }
+
+    /// すべての要素を先頭から順に掛けたものを返す。
+    pub fn fold(&self) -> M::Element {
+        match self.front.last() {
+            Some((_, front_fold)) => self.monoid.op(front_fold, &self.back_fold),
+            None => self.back_fold.clone(),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.front.clear();
+        self.back.clear();
+        self.back_fold = self.monoid.identity();
+    }
+}
+
+impl<M: Monoid> Extend<M::Element> for SlidingWindowAggregation<M> {
+    fn extend<I: IntoIterator<Item = M::Element>>(&mut self, iter: I) {
+        for x in iter {
+            self.push(x);
+        }
+    }
+}
+
+/// [`crate::IteratorExt::sliding_window_fold`]が返すイテレータ
+pub struct SlidingWindowFold<I, M: Monoid> {
+    iter: I,
+    width: usize,
+    window: SlidingWindowAggregation<M>,
+}
+
+impl<I, M: Monoid> SlidingWindowFold<I, M> {
+    pub(crate) fn new(iter: I, monoid: M, width: usize) -> Self {
+        assert_ne!(width, 0, "window width must be positive.");
+        Self {
+            iter,
+            width,
+            window: SlidingWindowAggregation::new(monoid),
+        }
+    }
+}
+
+impl<I, M> Iterator for SlidingWindowFold<I, M>
+where
+    I: Iterator<Item = M::Element>,
+    M: Monoid,
+{
+    type Item = M::Element;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.window.len() == self.width {
+            self.window.pop();
+        }
+        while self.window.len() < self.width {
+            self.window.push(self.iter.next()?);
+        }
+        Some(self.window.fold())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::IteratorExt;
+    use query::MonoidOp;
+    use rand::Rng;
+
+    #[test]
+    fn swag_test() {
+        // 非可換: 文字列の連結
+        let concat = MonoidOp::new(String::new(), |a: &String, b: &String| a.clone() + b);
+        let mut swag = SlidingWindowAggregation::new(&concat);
+        let mut naive = std::collections::VecDeque::new();
+        let mut rng = rand::thread_rng();
+        for i in 0..1000 {
+            if rng.gen_bool(0.55) {
+                let s = char::from(b'a' + (i % 26) as u8).to_string();
+                swag.push(s.clone());
+                naive.push_back(s);
+            } else {
+                assert_eq!(swag.pop(), naive.pop_front());
+            }
+            assert_eq!(swag.len(), naive.len());
+            assert_eq!(swag.fold(), naive.iter().cloned().collect::<String>());
+        }
+        swag.clear();
+        assert!(swag.is_empty());
+        assert_eq!(swag.fold(), "");
+        swag.extend(["x".to_string(), "y".to_string()]);
+        assert_eq!(swag.fold(), "xy");
+    }
+
+    #[test]
+    fn sliding_window_fold_test() {
+        let min = MonoidOp::new(i32::MAX, |a: &i32, b: &i32| *a.min(b));
+        let v = [5, 3, 8, 1, 9, 2, 7];
+        let res = v
+            .iter()
+            .copied()
+            .sliding_window_fold(min, 3)
+            .collect::<Vec<_>>();
+        assert_eq!(res, [3, 1, 1, 1, 2]);
+        let expected = v
+            .windows(3)
+            .map(|w| *w.iter().min().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(res, expected);
+        assert_eq!(v.iter().copied().sliding_window_fold(min, 7).count(), 1);
+        assert_eq!(v.iter().copied().sliding_window_fold(min, 8).count(), 0);
+        let res = v
+            .iter()
+            .copied()
+            .sliding_window_fold(min, 1)
+            .collect::<Vec<_>>();
+        assert_eq!(res, v);
+    }
+}